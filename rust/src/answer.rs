@@ -0,0 +1,156 @@
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
+/// The answer of a part. Numbers are kept as numbers, so they can be compared
+/// numerically and are written as numbers to json.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    /// Numbers not fitting into an [`Answer::Integer`]
+    BigInteger(i128),
+    Text(String),
+    /// Letters drawn with characters like '#', one string per line. It's up to the reader
+    /// to make out the letters.
+    Art(Vec<String>),
+}
+
+impl Answer {
+    /// Art from a drawing with one line per row. Trailing whitespace and empty lines
+    /// around it are dropped.
+    pub fn art(drawing: &str) -> Self {
+        let mut lines = drawing
+            .lines()
+            .map(|line| line.trim_end().to_owned())
+            .skip_while(|line| line.is_empty())
+            .collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        Answer::Art(lines)
+    }
+
+    /// The number, if the answer is one
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(number) => Some(*number as i128),
+            Answer::BigInteger(number) => Some(*number),
+            Answer::Text(_) | Answer::Art(_) => None,
+        }
+    }
+
+    /// The answer on a single line, for tables. Art is only a placeholder, its
+    /// drawing has to be printed on its own.
+    pub fn inline(&self) -> String {
+        match self {
+            Answer::Art(lines) => format!("<art, {} lines>", lines.len()),
+            answer => answer.to_string(),
+        }
+    }
+
+    /// Whether the answer is the expected text. Numbers are compared as numbers,
+    /// so e.g. "+5" matches 5, art ignores trailing whitespace.
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Integer(_) | Answer::BigInteger(_) => {
+                expected.trim().parse::<i128>().ok() == self.as_integer()
+            }
+            Answer::Text(text) => text.trim() == expected.trim(),
+            Answer::Art(_) => Answer::art(expected) == *self,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{}", number),
+            Answer::BigInteger(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Art(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(number) => serializer.serialize_i64(*number),
+            Answer::BigInteger(number) => serializer.serialize_i128(*number),
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Art(lines) => lines.serialize(serializer),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($integer:ty),*) => {$(
+        impl From<$integer> for Answer {
+            #[allow(irrefutable_let_patterns)]
+            fn from(number: $integer) -> Self {
+                if let Ok(number) = i64::try_from(number) {
+                    Answer::Integer(number)
+                } else if let Ok(number) = i128::try_from(number) {
+                    Answer::BigInteger(number)
+                } else {
+                    Answer::Text(number.to_string())
+                }
+            }
+        }
+    )*};
+}
+
+from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_by_size() {
+        assert_eq!(Answer::from(5usize), Answer::Integer(5));
+        assert_eq!(Answer::from(-5isize), Answer::Integer(-5));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn compare_answers() {
+        assert!(Answer::Integer(1557).matches("1557"));
+        assert!(Answer::Integer(1557).matches(" +1557\n"));
+        assert!(!Answer::Integer(1557).matches("1558"));
+        assert!(Answer::BigInteger(1 << 70).matches("1180591620717411303424"));
+        assert!(Answer::from("ABC").matches("ABC"));
+        assert!(!Answer::from("ABC").matches("abc"));
+
+        let art = Answer::art("\n#..#\n####  \n#..#\n");
+        assert_eq!(art.to_string(), "#..#\n####\n#..#");
+        assert_eq!(art.inline(), "<art, 3 lines>");
+        assert!(art.matches("#..#\n####\n#..#"));
+    }
+
+    #[test]
+    fn json() {
+        let answers = [
+            Answer::Integer(7),
+            Answer::BigInteger(1 << 70),
+            Answer::from("ABC"),
+            Answer::art("#.\n.#"),
+        ];
+        assert_eq!(
+            serde_json::to_string(&answers).unwrap(),
+            r##"[7,1180591620717411303424,"ABC",["#.",".#"]]"##
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt::Display, fs, path::Path};
+
+use crate::answer::Answer;
+
+/// Known correct answers keyed by day ("day1") and part ("part1")
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(HashMap<String, HashMap<String, ExpectedAnswer>>);
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExpectedAnswer {
+    Integer(i64),
+    Text(String),
+}
+
+impl Display for ExpectedAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedAnswer::Integer(number) => write!(f, "{}", number),
+            ExpectedAnswer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Outcome of comparing an answer with the expected one
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// No answer recorded for this day and part
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Reading answers file {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Parsing answers file {}", path.display()))
+    }
+
+    pub fn expected(&self, day: usize, part: usize) -> Option<String> {
+        self.0
+            .get(&format!("day{}", day))?
+            .get(&format!("part{}", part))
+            .map(|answer| answer.to_string())
+    }
+
+    pub fn check(&self, day: usize, part: usize, answer: &Answer) -> Verdict {
+        match self.expected(day, part) {
+            Some(expected) if answer.matches(&expected) => Verdict::Correct,
+            Some(expected) => Verdict::Wrong { expected },
+            None => Verdict::Unknown,
+        }
+    }
+}
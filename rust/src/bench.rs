@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::InputError;
+use crate::output::{csv_field, Format};
+use crate::runner::read_input;
+use crate::solution::{DynSolution, InputFiles};
+
+/// How often each solver gets repeated
+pub struct BenchConfig {
+    /// Unmeasured runs before the actual measurements
+    pub warmup: usize,
+    /// Amount of measured runs (ignored if a budget is set)
+    pub runs: usize,
+    /// Keep measuring until this much time was spent (at least one run)
+    pub budget: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(serialize_with = "as_nanos", deserialize_with = "from_nanos")]
+    pub min: Duration,
+    #[serde(serialize_with = "as_nanos", deserialize_with = "from_nanos")]
+    pub median: Duration,
+    #[serde(serialize_with = "as_nanos", deserialize_with = "from_nanos")]
+    pub mean: Duration,
+    #[serde(serialize_with = "as_nanos", deserialize_with = "from_nanos")]
+    pub stddev: Duration,
+}
+
+#[derive(Serialize)]
+pub struct BenchResult {
+    pub day: usize,
+    pub part: usize,
+    pub title: &'static str,
+    pub answer: Answer,
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+fn from_nanos<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_nanos)
+}
+
+impl Stats {
+    /// Statistics of at least one sample
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Self {
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Repeatedly parse the input and solve the part, measuring both steps separately
+pub fn bench_part(
+    solution: &dyn DynSolution,
+    input: &str,
+    part: usize,
+    config: &BenchConfig,
+) -> Result<BenchResult> {
+    let context = || format!("Benchmarking day {} part {}", solution.day(), part);
+
+    for _ in 0..config.warmup {
+        solution.solve_str(input, part).with_context(context)?;
+    }
+
+    let mut parse_samples = Vec::new();
+    let mut solve_samples = Vec::new();
+    let mut answer;
+    let started = Instant::now();
+    loop {
+        let start = Instant::now();
+        let parsed = solution.parse(input).with_context(context)?;
+        parse_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        answer = solution
+            .solve(parsed.as_ref(), part)
+            .with_context(context)?;
+        solve_samples.push(start.elapsed());
+
+        let done = match config.budget {
+            Some(budget) => started.elapsed() >= budget,
+            None => solve_samples.len() >= config.runs.max(1),
+        };
+        if done {
+            break;
+        }
+    }
+
+    Ok(BenchResult {
+        day: solution.day(),
+        part,
+        title: solution.title(),
+        answer,
+        runs: solve_samples.len(),
+        parse: Stats::new(parse_samples),
+        solve: Stats::new(solve_samples),
+    })
+}
+
+/// Benchmark the given parts of all solutions using their default input files.
+/// Failures are logged and skipped, they're returned after the results.
+pub fn bench_all(
+    solutions: &[&dyn DynSolution],
+    inputs: &InputFiles,
+    parts: &[usize],
+    config: &BenchConfig,
+) -> (Vec<BenchResult>, Vec<anyhow::Error>) {
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for solution in solutions {
+        let input = match read_input(&solution.default_input_path(inputs)) {
+            Ok(input) => input,
+            Err(e) => {
+                error!("Skipping day {}: {:#}", solution.day(), e);
+                errors.push(InputError::new(e).into());
+                continue;
+            }
+        };
+        for part in parts {
+            match bench_part(*solution, &input, *part, config) {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    error!("{:#}", e);
+                    errors.push(e);
+                }
+            }
+        }
+    }
+    (results, errors)
+}
+
+pub fn print_results(results: &[BenchResult], format: Format) -> Result<()> {
+    match format {
+        Format::Text => {
+            println!(
+                "Day  Part  {:>6}  {:>48}  {:>48}",
+                "Runs",
+                "Parse (min / median / mean / stddev)",
+                "Solve (min / median / mean / stddev)"
+            );
+            for result in results {
+                println!(
+                    "{:>3}  {:>4}  {:>6}  {:>48}  {:>48}",
+                    result.day,
+                    result.part,
+                    result.runs,
+                    format_stats(&result.parse),
+                    format_stats(&result.solve)
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(results)?),
+        Format::Csv => {
+            println!(
+                "day,part,title,answer,runs,\
+                 parse_min,parse_median,parse_mean,parse_stddev,\
+                 solve_min,solve_median,solve_mean,solve_stddev"
+            );
+            for result in results {
+                println!(
+                    "{},{},{},{},{},{},{}",
+                    result.day,
+                    result.part,
+                    csv_field(result.title),
+                    csv_field(&result.answer.to_string()),
+                    result.runs,
+                    csv_stats(&result.parse),
+                    csv_stats(&result.solve)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Nanoseconds, like the durations in json
+fn csv_stats(stats: &Stats) -> String {
+    format!(
+        "{},{},{},{}",
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos(),
+        stats.stddev.as_nanos()
+    )
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "{:?} / {:?} / {:?} / {:?}",
+        stats.min, stats.median, stats.mean, stats.stddev
+    )
+}
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::solution::InputFiles;
+use crate::submissions::Response;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Talks to the Advent of Code website (or anything serving the same paths)
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: usize,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// `session` is the value of the "session" cookie of a logged in browser
+    pub fn new(base_url: &str, session: &str, year: usize) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!(
+                    env!("CARGO_PKG_NAME"),
+                    "/",
+                    env!("CARGO_PKG_VERSION")
+                ))
+                .build(),
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        self.request("GET", path, None)
+    }
+
+    fn request(&self, method: &str, path: &str, form: Option<&[(&str, &str)]>) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("{} {}", method, url);
+        let request = self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        let response = response.map_err(|e| match e {
+            ureq::Error::Status(400, _) => anyhow!("Session token rejected by {}", url),
+            ureq::Error::Status(404, _) => anyhow!("Nothing found at {} (yet?)", url),
+            e => anyhow::Error::new(e),
+        })?;
+        response
+            .into_string()
+            .with_context(|| format!("Reading response of {}", url))
+    }
+
+    /// Download the puzzle input of a day
+    pub fn input(&self, day: usize) -> Result<String> {
+        self.get(&format!("/{}/day/{}/input", self.year, day))
+    }
+
+    /// Submit the answer of a part and tell what the website thinks about it
+    pub fn submit(&self, day: usize, part: usize, answer: &str) -> Result<Response> {
+        let html = self.request(
+            "POST",
+            &format!("/{}/day/{}/answer", self.year, day),
+            Some(&[("level", &part.to_string()), ("answer", answer)]),
+        )?;
+        Ok(Response::parse(&html))
+    }
+}
+
+/// Read the session token from a file, e.g. one containing nothing but the token
+pub fn read_session(session_file: &Path) -> Result<String> {
+    let session = fs::read_to_string(session_file)
+        .with_context(|| format!("Reading session token from {}", session_file.display()))?;
+    let session = session.trim();
+    ensure!(
+        !session.is_empty(),
+        "{} contains no session token!",
+        session_file.display()
+    );
+    Ok(session.to_owned())
+}
+
+/// The input file of a day if it was downloaded before.
+/// Empty files (as created by the `new` command) don't count.
+pub fn cached_input(inputs: &InputFiles, year: usize, day: usize) -> Option<PathBuf> {
+    let path = inputs.path(year, day);
+    fs::metadata(&path)
+        .is_ok_and(|m| m.len() > 0)
+        .then_some(path)
+}
+
+/// Download the input of a day into the input directory unless it's already there
+pub fn fetch_input(client: &Client, inputs: &InputFiles, day: usize) -> Result<PathBuf> {
+    if let Some(path) = cached_input(inputs, client.year, day) {
+        return Ok(path);
+    }
+
+    let path = inputs.path(client.year, day);
+    let input = client.input(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Creating input directory {}", dir.display()))?;
+    }
+    fs::write(&path, input).with_context(|| format!("Writing {}", path.display()))?;
+    Ok(path)
+}
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use std::{path::Path, process::Command};
+
+use crate::answer::Answer;
+
+/// The Go implementation in this repository, which only knows some days.
+/// Built from source with `go run`, as the committed `go/main` binary lags behind it.
+pub const GO_COMMAND: &str = concat!(
+    "go -C ",
+    env!("CARGO_MANIFEST_DIR"),
+    "/../go run . {day} {part} -f {input}"
+);
+
+/// Line prefix the Go implementation prints its answer with
+pub const GO_ANSWER_PREFIX: &str = "Solved: ";
+
+/// Another implementation of the puzzles, run as a separate process
+pub struct ExternalSolver {
+    /// Program and arguments, with "{day}", "{part}" and "{input}" as placeholders
+    args: Vec<String>,
+    /// The answer is the line starting with this, or the last line if empty
+    answer_prefix: String,
+}
+
+impl ExternalSolver {
+    /// `command` is split at whitespace, e.g. "../go/main {day} {part} -f {input}"
+    pub fn new(command: &str, answer_prefix: &str) -> Result<Self> {
+        let args = command
+            .split_whitespace()
+            .map(|arg| arg.to_owned())
+            .collect::<Vec<_>>();
+        ensure!(!args.is_empty(), "The command to compare with is empty!");
+        Ok(Self {
+            args,
+            answer_prefix: answer_prefix.to_owned(),
+        })
+    }
+
+    pub fn solve(&self, day: usize, part: usize, input: &Path) -> Result<String> {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| {
+                arg.replace("{day}", &day.to_string())
+                    .replace("{part}", &part.to_string())
+                    .replace("{input}", &input.display().to_string())
+            })
+            .collect::<Vec<_>>();
+        debug!("Running {:?}", args);
+        let output = Command::new(&args[0])
+            .args(&args[1..])
+            .output()
+            .with_context(|| format!("Running {}", args[0]))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        ensure!(
+            output.status.success(),
+            "{} failed with {}: {}",
+            args[0],
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        extract_answer(&stdout, &self.answer_prefix)
+    }
+}
+
+/// Find the answer in the output. Without a matching line the last line tells what went wrong.
+fn extract_answer(stdout: &str, answer_prefix: &str) -> Result<String> {
+    let mut lines = stdout
+        .lines()
+        .map(|line| line.trim())
+        .filter(|l| !l.is_empty());
+    if answer_prefix.is_empty() {
+        return lines
+            .next_back()
+            .map(|line| line.to_owned())
+            .ok_or(anyhow!("No output"));
+    }
+    let last_line = lines.clone().next_back().unwrap_or("No output").to_owned();
+    lines
+        .find_map(|line| line.strip_prefix(answer_prefix.trim()))
+        .map(|answer| answer.trim().to_owned())
+        .ok_or(anyhow!("{}", last_line))
+}
+
+/// Whether two implementations agree on a part
+pub enum Agreement {
+    Agree,
+    Differ,
+    /// At least one side couldn't solve the part, so there's nothing to compare
+    Failed,
+}
+
+impl Agreement {
+    /// The other implementation only gives text, numbers get compared as numbers
+    pub fn of(ours: &Result<Answer>, theirs: &Result<String>) -> Self {
+        match (ours, theirs) {
+            (Ok(ours), Ok(theirs)) if ours.matches(theirs) => Agreement::Agree,
+            (Ok(_), Ok(_)) => Agreement::Differ,
+            _ => Agreement::Failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_from_output() {
+        let output = "Using file \"../input/day1.txt\"\nSolved: 1557\n";
+        assert_eq!(extract_answer(output, GO_ANSWER_PREFIX).unwrap(), "1557");
+        assert_eq!(extract_answer(output, "").unwrap(), "Solved: 1557");
+
+        let output = "Using file \"x\"\nDay 3 part 1 is not implemented, yet.\n";
+        let error = extract_answer(output, GO_ANSWER_PREFIX).unwrap_err();
+        assert_eq!(error.to_string(), "Day 3 part 1 is not implemented, yet.");
+    }
+}
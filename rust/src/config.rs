@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::output::Format;
+
+/// Name of the config file looked for in the current directory and the repository root
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Project defaults, e.g.
+///
+/// ```toml
+/// input_dir = "input"
+/// input_pattern = "day{day}.txt"
+/// log_level = "debug"
+/// format = "json"
+/// year = 2021
+///
+/// [bench]
+/// warmup = 3
+/// runs = 100
+/// ```
+///
+/// Everything is optional and command line options take precedence.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Relative to the directory of the config file
+    pub input_dir: Option<PathBuf>,
+    /// File names of the inputs, "{day}" gets replaced by the day
+    pub input_pattern: Option<String>,
+    /// Used unless RUST_LOG is set
+    pub log_level: Option<String>,
+    pub format: Option<Format>,
+    pub year: Option<usize>,
+    pub bench: BenchSettings,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchSettings {
+    pub warmup: Option<usize>,
+    pub runs: Option<usize>,
+    /// Seconds per solver
+    pub budget: Option<f64>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Reading config file {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("Parsing config file {}", path.display()))?;
+        if let (Some(input_dir), Some(config_dir)) = (&config.input_dir, path.parent()) {
+            config.input_dir = Some(config_dir.join(input_dir));
+        }
+        Ok(config)
+    }
+}
+
+/// The config file in the current directory, else the one in the repository root
+pub fn find() -> Option<PathBuf> {
+    [
+        PathBuf::from(FILE_NAME),
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).join(FILE_NAME),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config: Config = toml::from_str(
+            r#"
+            input_dir = "inputs"
+            input_pattern = "{day}.txt"
+            format = "csv"
+            year = 2020
+
+            [bench]
+            runs = 10
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.input_dir, Some(PathBuf::from("inputs")));
+        assert_eq!(config.input_pattern.as_deref(), Some("{day}.txt"));
+        assert_eq!(config.format, Some(Format::Csv));
+        assert_eq!(config.year, Some(2020));
+        assert_eq!(config.bench.runs, Some(10));
+        assert_eq!(config.bench.warmup, None);
+        assert_eq!(config.log_level, None);
+
+        assert!(toml::from_str::<Config>("input_directory = \"x\"").is_err());
+    }
+}
@@ -1,13 +1,18 @@
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
+use crate::answer::Answer;
+use crate::input;
+use crate::solution::Solution;
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<u64>> {
+    Ok(input::lines(input)
+        .map(|line| line.parse(line.text))
+        .collect::<Result<_, _>>()?)
+}
+
+pub fn solve_part_1(measurements: &[u64]) -> anyhow::Result<Answer> {
     let mut increments: usize = 0;
     let mut last_measurement: Option<u64> = None;
 
-    for line in input.split('\n').map(|s| s.trim()) {
-        if line.is_empty() {
-            continue;
-        }
-        let measurement = line.parse()?;
-
+    for &measurement in measurements {
         if let Some(last_measurement) = last_measurement {
             if measurement > last_measurement {
                 increments += 1;
@@ -15,20 +20,15 @@ pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
         }
         last_measurement = Some(measurement);
     }
-    Ok(increments)
+    Ok(increments.into())
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
+pub fn solve_part_2(measurements: &[u64]) -> anyhow::Result<Answer> {
     let mut increments: usize = 0;
     let mut last_measurement_sum: Option<u64> = None;
     let mut window = (None::<u64>, None::<u64>, None::<u64>);
 
-    for line in input.split('\n').map(|s| s.trim()) {
-        if line.is_empty() {
-            continue;
-        }
-        let measurement = line.parse()?;
-
+    for &measurement in measurements {
         window.0 = window.1;
         window.1 = window.2;
         window.2 = Some(measurement);
@@ -48,5 +48,52 @@ pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
         }
         last_measurement_sum = measurement_sum;
     }
-    Ok(increments)
+    Ok(increments.into())
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u64>;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+
+    fn parse(input: &str) -> anyhow::Result<Vec<u64>> {
+        parse_input(input)
+    }
+
+    fn part1(measurements: &Vec<u64>) -> anyhow::Result<Answer> {
+        solve_part_1(measurements)
+    }
+
+    fn part2(measurements: &Vec<u64>) -> anyhow::Result<Answer> {
+        solve_part_2(measurements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn part_1_example() {
+        let measurements = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&measurements).unwrap().to_string(), "7");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let measurements = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&measurements).unwrap().to_string(), "5");
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let measurements = parse_input(&EXAMPLE.replace('\n', "\r\n")).unwrap();
+        assert_eq!(measurements.len(), 10);
+    }
 }
@@ -1,46 +1,125 @@
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::{self, Line};
+use crate::solution::Solution;
+
+pub enum Command {
+    Forward(isize),
+    Down(isize),
+    Up(isize),
+}
+
+impl Command {
+    /// Parse a line like "forward 5"
+    pub fn parse_line(line: &Line) -> Result<Self, ParseError> {
+        let (direction, value) = line
+            .text
+            .split_once(' ')
+            .ok_or_else(|| line.error(line.text, "No value"))?;
+        let value: isize = line.parse(value)?;
+        match direction {
+            "forward" => Ok(Command::Forward(value)),
+            "down" => Ok(Command::Down(value)),
+            "up" => Ok(Command::Up(value)),
+            _ => Err(line.error(direction, format!("Unexpected command: {}", direction))),
+        }
+    }
+}
+
+impl std::str::FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        Command::parse_line(&Line::new(0, line))
+    }
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<Command>> {
+    Ok(input::lines(input)
+        .map(|line| Command::parse_line(&line))
+        .collect::<Result<_, _>>()?)
+}
+
+pub fn solve_part_1(commands: &[Command]) -> anyhow::Result<Answer> {
     let (mut horizontal, mut depth) = (0isize, 0isize);
 
-    for line in input
-        .split('\n')
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-    {
-        let value: isize = line.split(" ").last().ok_or(anyhow!("No value"))?.parse()?;
-        if line.starts_with("forward") {
-            horizontal += value;
-        } else if line.starts_with("down") {
-            depth += value;
-        } else if line.starts_with("up") {
-            depth -= value;
-        } else {
-            bail!("Unexpected command: {}", value)
+    for command in commands {
+        match command {
+            Command::Forward(value) => horizontal += value,
+            Command::Down(value) => depth += value,
+            Command::Up(value) => depth -= value,
         }
     }
 
-    Ok(horizontal * depth)
+    Ok((horizontal * depth).into())
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
+pub fn solve_part_2(commands: &[Command]) -> anyhow::Result<Answer> {
     let (mut horizontal, mut depth, mut aim) = (0isize, 0isize, 0isize);
 
-    for line in input
-        .split('\n')
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-    {
-        let value: isize = line.split(" ").last().ok_or(anyhow!("No value"))?.parse()?;
-        if line.starts_with("forward") {
-            horizontal += value;
-            depth += aim * value;
-        } else if line.starts_with("down") {
-            aim += value;
-        } else if line.starts_with("up") {
-            aim -= value;
-        } else {
-            bail!("Unexpected command: {}", value)
+    for command in commands {
+        match command {
+            Command::Forward(value) => {
+                horizontal += value;
+                depth += aim * value;
+            }
+            Command::Down(value) => aim += value,
+            Command::Up(value) => aim -= value,
         }
     }
 
-    Ok(horizontal * depth)
+    Ok((horizontal * depth).into())
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Command>;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 2;
+    const TITLE: &'static str = "Dive!";
+
+    fn parse(input: &str) -> anyhow::Result<Vec<Command>> {
+        parse_input(input)
+    }
+
+    fn part1(commands: &Vec<Command>) -> anyhow::Result<Answer> {
+        solve_part_1(commands)
+    }
+
+    fn part2(commands: &Vec<Command>) -> anyhow::Result<Answer> {
+        solve_part_2(commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
+    #[test]
+    fn part_1_example() {
+        let commands = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&commands).unwrap().to_string(), "150");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let commands = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&commands).unwrap().to_string(), "900");
+    }
+
+    #[test]
+    fn unexpected_command() {
+        assert!(parse_input("backward 3").is_err());
+    }
 }
@@ -1,45 +1,66 @@
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    let input = input.replace("\r", ""); // Windows safety
+use crate::answer::Answer;
+use crate::input;
+use crate::solution::Solution;
 
-    let bit_count = input.chars().position(|c| c == '\n').unwrap();
+/// The diagnostic report: Binary numbers which all have the same amount of bits
+pub struct Report {
+    pub bit_count: usize,
+    pub numbers: Vec<usize>,
+}
+
+pub fn parse_input(input: &str) -> anyhow::Result<Report> {
+    let bit_count = input::first_line(input)
+        .ok_or(anyhow!("Expected at least one line!"))?
+        .text
+        .len();
+    let mut numbers = Vec::new();
+
+    for line in input::lines(input) {
+        let text = line.text;
+        if let Some((pos, char)) = text.char_indices().find(|(_, c)| *c != '0' && *c != '1') {
+            let span = &text[pos..pos + char.len_utf8()];
+            Err(line.error(span, format!("Unexpected char: {}", char)))?;
+        }
+        if text.len() != bit_count {
+            Err(line.error(
+                text,
+                format!("Expected {} bits but got {}", bit_count, text.len()),
+            ))?;
+        }
+        numbers.push(usize::from_str_radix(text, 2).map_err(|e| line.error(text, e))?);
+    }
+    Ok(Report { bit_count, numbers })
+}
+
+pub fn solve_part_1(report: &Report) -> anyhow::Result<Answer> {
+    let bit_count = report.bit_count;
     let mut bits = vec![(0usize, 0usize); bit_count];
 
-    for line in input
-        .split('\n')
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-    {
-        for (bit, char) in line.chars().enumerate() {
-            match char {
-                '0' => bits[bit].0 += 1,
-                '1' => bits[bit].1 += 1,
-                _ => bail!("Unexpected char: {}", char),
+    for number in &report.numbers {
+        for (bit, counts) in bits.iter_mut().enumerate() {
+            if number & (1 << ((bit_count - 1) - bit)) != 0 {
+                counts.1 += 1;
+            } else {
+                counts.0 += 1;
             }
         }
     }
 
     let mut gamma: usize = 0;
     let mut epsilon: usize = 0;
-    for bit in 0..bit_count {
-        if bits[bit].0 > bits[bit].1 {
+    for (bit, counts) in bits.iter().enumerate() {
+        if counts.0 > counts.1 {
             epsilon |= 1 << ((bit_count - 1) - bit);
         } else {
             gamma |= 1 << ((bit_count - 1) - bit);
         }
     }
-    Ok(gamma * epsilon)
+    Ok((gamma * epsilon).into())
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    let input = input.replace("\r", ""); // Windows safety
-
-    let bit_count = input.chars().position(|c| c == '\n').unwrap();
-    let numbers: Vec<usize> = input
-        .split("\n")
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| usize::from_str_radix(l, 2).unwrap())
-        .collect();
+pub fn solve_part_2(report: &Report) -> anyhow::Result<Answer> {
+    let bit_count = report.bit_count;
+    let numbers = &report.numbers;
 
     // Find oxygen generator rating
     let mut oxygen_generator_rating: Option<usize> = None;
@@ -100,5 +121,65 @@ pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
     let co2_scrubber_rating =
         co2_scrubber_rating.ok_or(anyhow!("No CO2 scrubber rating found!"))?;
 
-    Ok(oxygen_generator_rating * co2_scrubber_rating)
+    Ok((oxygen_generator_rating * co2_scrubber_rating).into())
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Report;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
+
+    fn parse(input: &str) -> anyhow::Result<Report> {
+        parse_input(input)
+    }
+
+    fn part1(report: &Report) -> anyhow::Result<Answer> {
+        solve_part_1(report)
+    }
+
+    fn part2(report: &Report) -> anyhow::Result<Answer> {
+        solve_part_2(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn part_1_example() {
+        let report = parse_input(EXAMPLE).unwrap();
+        assert_eq!(report.bit_count, 5);
+        assert_eq!(solve_part_1(&report).unwrap().to_string(), "198");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let report = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&report).unwrap().to_string(), "230");
+    }
+
+    #[test]
+    fn unexpected_char() {
+        assert!(parse_input("00100\n01201\n").is_err());
+    }
 }
@@ -1,8 +1,13 @@
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::{self, Line};
+use crate::solution::Solution;
 use anyhow::Result;
 use std::result::Result as StdResult;
 
-type BingoNumber = u8;
+pub type BingoNumber = u8;
 
+#[derive(Clone)]
 struct BingoField {
     number: BingoNumber,
     marked: bool,
@@ -21,7 +26,7 @@ impl std::fmt::Display for BingoField {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut text = format!("{:2}", self.number);
         if self.marked {
-            text = String::from_iter(text.chars().into_iter().map(|char| match char {
+            text = String::from_iter(text.chars().map(|char| match char {
                 '1' => '\u{2776}',
                 '2' => '\u{2777}',
                 '3' => '\u{2778}',
@@ -49,64 +54,44 @@ impl std::fmt::Display for BingoField {
     }
 }
 
-struct BingoCard {
+#[derive(Clone)]
+pub struct BingoCard {
     grid: [[BingoField; 5]; 5],
     last_marked_number: Option<BingoNumber>,
 }
 
 impl BingoCard {
     pub fn from_lines(line_set: &[&str]) -> Result<Self> {
-        let numbers = line_set
+        let lines = line_set
             .iter()
-            .map(|l| l.replace("\n", " "))
-            .flat_map(|l| l.split(" ").map(|n| n.to_owned()).collect::<Vec<_>>())
-            .filter(|word| !word.is_empty())
-            .map(|word| word.parse())
-            .collect::<StdResult<Vec<BingoNumber>, _>>()?;
-
-        ensure!(
-            numbers.len() == 5 * 5,
-            "Wrong amount of numbers for a 5x5 bingo card!"
-        );
+            .enumerate()
+            .map(|(index, raw)| Line::new(index, raw))
+            .collect::<Vec<_>>();
+        Ok(Self::parse_lines(&lines)?)
+    }
+
+    /// Parse a card from a block of input lines
+    fn parse_lines(lines: &[Line]) -> StdResult<Self, ParseError> {
+        let mut numbers: Vec<BingoNumber> = Vec::with_capacity(5 * 5);
+        for line in lines {
+            for word in line.text.split(' ').filter(|word| !word.is_empty()) {
+                numbers.push(line.parse(word)?);
+            }
+        }
+
+        if numbers.len() != 5 * 5 {
+            let first_line = lines.first().copied().unwrap_or(Line::new(0, ""));
+            return Err(first_line.error(
+                first_line.text,
+                format!(
+                    "Wrong amount of numbers for a 5x5 bingo card! Found {} numbers in this card.",
+                    numbers.len()
+                ),
+            ));
+        }
 
         Ok(Self {
-            grid: [
-                [
-                    numbers[0 * 5 + 0].into(),
-                    numbers[1 * 5 + 0].into(),
-                    numbers[2 * 5 + 0].into(),
-                    numbers[3 * 5 + 0].into(),
-                    numbers[4 * 5 + 0].into(),
-                ],
-                [
-                    numbers[0 * 5 + 1].into(),
-                    numbers[1 * 5 + 1].into(),
-                    numbers[2 * 5 + 1].into(),
-                    numbers[3 * 5 + 1].into(),
-                    numbers[4 * 5 + 1].into(),
-                ],
-                [
-                    numbers[0 * 5 + 2].into(),
-                    numbers[1 * 5 + 2].into(),
-                    numbers[2 * 5 + 2].into(),
-                    numbers[3 * 5 + 2].into(),
-                    numbers[4 * 5 + 2].into(),
-                ],
-                [
-                    numbers[0 * 5 + 3].into(),
-                    numbers[1 * 5 + 3].into(),
-                    numbers[2 * 5 + 3].into(),
-                    numbers[3 * 5 + 3].into(),
-                    numbers[4 * 5 + 3].into(),
-                ],
-                [
-                    numbers[0 * 5 + 4].into(),
-                    numbers[1 * 5 + 4].into(),
-                    numbers[2 * 5 + 4].into(),
-                    numbers[3 * 5 + 4].into(),
-                    numbers[4 * 5 + 4].into(),
-                ],
-            ],
+            grid: std::array::from_fn(|x| std::array::from_fn(|y| numbers[y * 5 + x].into())),
             last_marked_number: None,
         })
     }
@@ -156,9 +141,9 @@ impl BingoCard {
 impl std::fmt::Display for BingoCard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..5 {
-            write!(
+            writeln!(
                 f,
-                "{} {} {} {} {}\n",
+                "{} {} {} {} {}",
                 self.grid[0][y], self.grid[1][y], self.grid[2][y], self.grid[3][y], self.grid[4][y]
             )?;
         }
@@ -166,33 +151,43 @@ impl std::fmt::Display for BingoCard {
     }
 }
 
-fn parse_input(input: &str) -> Result<(Vec<BingoNumber>, Vec<BingoCard>)> {
-    let input = input.replace("\r", ""); // Windows safety
-    let line_sets = input
-        .split("\n\n")
-        .map(|s| s.split("\n").collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let called_numbers = line_sets[0][0]
-        .split(",")
-        .map(|n| n.parse())
-        .collect::<StdResult<Vec<BingoNumber>, _>>()?;
+/// The numbers to call in order and all participating cards
+pub struct Bingo {
+    pub called_numbers: Vec<BingoNumber>,
+    pub cards: Vec<BingoCard>,
+}
+
+pub fn parse_input(input: &str) -> Result<Bingo> {
+    // The called numbers and the cards are separated by empty lines
+    let blocks = input::blocks(input);
+    let (first_block, card_blocks) = blocks
+        .split_first()
+        .ok_or(anyhow!("Expected the called numbers!"))?;
+    let called_numbers = first_block[0].numbers(',')?;
+    if let Some(line) = first_block.get(1) {
+        Err(line.error(line.text, "Expected an empty line after the called numbers"))?;
+    }
 
-    let cards: Vec<BingoCard> = line_sets[1..]
+    let cards = card_blocks
         .iter()
-        .map(|ls| BingoCard::from_lines(ls))
-        .collect::<StdResult<Vec<BingoCard>, _>>()?;
-    Ok((called_numbers, cards))
+        .map(|block| BingoCard::parse_lines(block))
+        .collect::<StdResult<Vec<_>, _>>()?;
+
+    Ok(Bingo {
+        called_numbers,
+        cards,
+    })
 }
 
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    let (called_numbers, mut cards) = parse_input(input)?;
+pub fn solve_part_1(bingo: &Bingo) -> anyhow::Result<Answer> {
+    let mut cards = bingo.cards.clone();
 
-    for called_number in called_numbers {
+    for &called_number in &bingo.called_numbers {
         for card in &mut cards {
             card.mark(called_number);
             if let Some(score) = card.calc_score() {
                 debug!("Solved:\n{}", card);
-                return Ok(score);
+                return Ok(score.into());
             }
         }
     }
@@ -200,20 +195,185 @@ pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
     Err(anyhow!("No card ever won!"))
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    let (called_numbers, mut cards) = parse_input(input)?;
+pub fn solve_part_2(bingo: &Bingo) -> anyhow::Result<Answer> {
+    let mut cards = bingo.cards.clone();
 
-    for called_number in called_numbers {
+    for &called_number in &bingo.called_numbers {
         cards.iter_mut().for_each(|card| card.mark(called_number));
-        let drained = cards
-            .drain_filter(|card| card.is_bingo())
-            .collect::<Vec<_>>();
+        let drained = drain_bingo_cards(&mut cards);
         if cards.is_empty() && drained.len() == 1 {
-            return Ok(drained[0]
+            return drained[0]
                 .calc_score()
-                .ok_or(anyhow!("Expected card to have a score!"))?);
+                .map(Answer::from)
+                .ok_or(anyhow!("Expected card to have a score!"));
         }
     }
 
     Err(anyhow!("Found no single card remaining!"))
 }
+
+/// Remove all cards that have a bingo and return them
+#[cfg(not(feature = "nightly"))]
+fn drain_bingo_cards(cards: &mut Vec<BingoCard>) -> Vec<BingoCard> {
+    cards.extract_if(.., |card| card.is_bingo()).collect()
+}
+
+/// Remove all cards that have a bingo and return them.
+/// Partitions in place, so the remaining cards don't have to be shifted one by one.
+#[cfg(feature = "nightly")]
+fn drain_bingo_cards(cards: &mut Vec<BingoCard>) -> Vec<BingoCard> {
+    let remaining = cards.iter_mut().partition_in_place(|card| !card.is_bingo());
+    cards.split_off(remaining)
+}
+
+/// The cards after the first `step` numbers were called
+fn cards_after(bingo: &Bingo, step: usize) -> Vec<BingoCard> {
+    let mut cards = bingo.cards.clone();
+    for &called_number in bingo.called_numbers.iter().take(step) {
+        cards.iter_mut().for_each(|card| card.mark(called_number));
+    }
+    cards
+}
+
+/// Repl commands: `show card <n>`, `show numbers` and `step`
+pub fn explore(bingo: &Bingo, step: usize, command: &[&str]) -> Option<Result<String>> {
+    let join = |numbers: &[BingoNumber]| {
+        numbers
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let step = step.min(bingo.called_numbers.len());
+    match command {
+        ["show", "card", n] => Some(n.parse::<usize>().map_err(|e| anyhow!(e)).and_then(|n| {
+            let cards = cards_after(bingo, step);
+            let card = n
+                .checked_sub(1)
+                .and_then(|i| cards.get(i))
+                .ok_or(anyhow!("Only cards 1 to {} exist!", cards.len()))?;
+            Ok(card.to_string())
+        })),
+        ["show", "numbers"] => Some(Ok(format!(
+            "Called: {}\nRemaining: {}",
+            join(&bingo.called_numbers[..step]),
+            join(&bingo.called_numbers[step..])
+        ))),
+        ["step"] if step == 0 => Some(Ok("No numbers called yet".to_owned())),
+        ["step"] => {
+            let before = cards_after(bingo, step - 1);
+            let after = cards_after(bingo, step);
+            let mut text = format!(
+                "Called {} ({}/{})",
+                bingo.called_numbers[step - 1],
+                step,
+                bingo.called_numbers.len()
+            );
+            for (i, (before, after)) in before.iter().zip(&after).enumerate() {
+                if let (false, Some(score)) = (before.is_bingo(), after.calc_score()) {
+                    text += &format!("\nCard {} has a bingo! Score: {}", i + 1, score);
+                }
+            }
+            let remaining = after.iter().filter(|card| !card.is_bingo()).count();
+            text += &format!("\n{} cards without a bingo", remaining);
+            Some(Ok(text))
+        }
+        _ => None,
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Bingo;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 4;
+    const TITLE: &'static str = "Giant Squid";
+
+    fn parse(input: &str) -> anyhow::Result<Bingo> {
+        parse_input(input)
+    }
+
+    fn part1(bingo: &Bingo) -> anyhow::Result<Answer> {
+        solve_part_1(bingo)
+    }
+
+    fn part2(bingo: &Bingo) -> anyhow::Result<Answer> {
+        solve_part_2(bingo)
+    }
+
+    fn explore(bingo: &Bingo, step: usize, command: &[&str]) -> Option<Result<String>> {
+        explore(bingo, step, command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn part_1_example() {
+        let bingo = parse_input(EXAMPLE).unwrap();
+        assert_eq!(bingo.cards.len(), 3);
+        assert_eq!(solve_part_1(&bingo).unwrap().to_string(), "4512");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let bingo = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&bingo).unwrap().to_string(), "1924");
+    }
+
+    #[test]
+    fn bingo_in_column() {
+        let mut card = parse_input(EXAMPLE).unwrap().cards.remove(0);
+        for number in [13, 2, 9, 10] {
+            card.mark(number);
+            assert!(!card.is_bingo());
+        }
+        card.mark(12);
+        assert!(card.is_bingo());
+        assert_eq!(card.calc_score(), Some(12 * (300 - 13 - 2 - 9 - 10 - 12)));
+    }
+
+    #[test]
+    fn explore_steps() {
+        let bingo = parse_input(EXAMPLE).unwrap();
+        let step = |step| explore(&bingo, step, &["step"]).unwrap().unwrap();
+        assert_eq!(step(1), "Called 7 (1/27)\n3 cards without a bingo");
+        assert_eq!(
+            step(12),
+            "Called 24 (12/27)\nCard 3 has a bingo! Score: 4512\n2 cards without a bingo"
+        );
+        assert!(explore(&bingo, 0, &["show", "card", "4"]).unwrap().is_err());
+        assert!(explore(&bingo, 0, &["unknown"]).is_none());
+    }
+
+    #[test]
+    fn wrong_card_size() {
+        assert!(BingoCard::from_lines(&["1 2 3 4 5"]).is_err());
+    }
+}
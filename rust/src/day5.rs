@@ -1,12 +1,16 @@
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::input::{self, Line};
+use crate::solution::Solution;
 use anyhow::Result;
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::{result::Result as StdResult, str::FromStr};
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Pos {
-    x: i16,
-    y: i16,
+pub struct Pos {
+    pub x: i16,
+    pub y: i16,
 }
 
 impl Display for Pos {
@@ -49,7 +53,7 @@ impl std::ops::SubAssign for Pos {
     }
 }
 
-struct VentLine {
+pub struct VentLine {
     from: Pos,
     to: Pos,
 }
@@ -61,31 +65,33 @@ impl Display for VentLine {
 }
 
 impl FromStr for VentLine {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        ensure!(s.contains(" -> "));
-        let numbers = s
-            .replace(" -> ", ",")
-            .split(",")
-            .map(|n| n.parse())
-            .collect::<StdResult<Vec<i16>, _>>()?;
-        ensure!(numbers.len() == 4, "Invalid format!");
-        VentLine::new(
-            Pos {
-                x: numbers[0],
-                y: numbers[1],
-            },
-            Pos {
-                x: numbers[2],
-                y: numbers[3],
-            },
-        )
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> StdResult<Self, ParseError> {
+        VentLine::parse_line(&Line::new(0, s))
     }
 }
 
 impl VentLine {
-    fn new(from: Pos, to: Pos) -> Result<Self> {
+    /// Parse a line like "0,9 -> 5,9"
+    pub fn parse_line(line: &Line) -> StdResult<Self, ParseError> {
+        let (from, to) = line
+            .text
+            .split_once(" -> ")
+            .ok_or_else(|| line.error(line.text, "Invalid format! Expected \"x,y -> x,y\""))?;
+        let parse_pos = |pos: &str| {
+            let (x, y) = pos
+                .split_once(',')
+                .ok_or_else(|| line.error(pos, "Invalid format! Expected \"x,y\""))?;
+            Ok(Pos {
+                x: line.parse(x)?,
+                y: line.parse(y)?,
+            })
+        };
+        VentLine::new(parse_pos(from)?, parse_pos(to)?).map_err(|e| line.error(line.text, e))
+    }
+
+    pub fn new(from: Pos, to: Pos) -> Result<Self> {
         let vent_line = VentLine { from, to };
         ensure!(
             vent_line.is_ortogonal() || vent_line.is_diagnonal(),
@@ -94,23 +100,23 @@ impl VentLine {
         Ok(vent_line)
     }
 
-    fn iter_positions(&self) -> Result<VentLineIterator> {
+    pub fn iter_positions(&self) -> Result<VentLineIterator> {
         VentLineIterator::new(self)
     }
 
     /// Check if other point is either 0°, 90°, 180° or 270° from the given point
-    fn is_ortogonal(&self) -> bool {
+    pub fn is_ortogonal(&self) -> bool {
         self.from.x == self.to.x || self.from.y == self.to.y
     }
 
     /// Check if other point is either 45°, 135°, 225° or 315° from the given point
-    fn is_diagnonal(&self) -> bool {
+    pub fn is_diagnonal(&self) -> bool {
         let vec = self.from - self.to;
         !self.is_ortogonal() && vec.x.abs() == vec.y.abs()
     }
 }
 
-struct VentLineIterator {
+pub struct VentLineIterator {
     last: Pos,
     /// Only -1, 0 or 1 for points expected!
     vec: Pos,
@@ -139,21 +145,17 @@ impl Iterator for VentLineIterator {
             return None;
         }
         self.last += self.vec;
-        Some(self.last.clone())
+        Some(self.last)
     }
 }
 
-fn parse_input(input: &str) -> Result<Vec<VentLine>> {
-    input
-        .replace("\r", "")
-        .split("\n")
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| l.parse::<VentLine>())
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<VentLine>> {
+    Ok(input::lines(input)
+        .map(|line| VentLine::parse_line(&line))
+        .collect::<StdResult<_, _>>()?)
 }
 
-fn count_collisions(vent_lines: &[VentLine]) -> Result<usize> {
+pub fn count_collisions<'a>(vent_lines: impl IntoIterator<Item = &'a VentLine>) -> Result<usize> {
     let mut pos_count = HashMap::<Pos, usize>::new();
     for vent_line in vent_lines {
         //debug!("Line: {}", vent_line);
@@ -166,12 +168,147 @@ fn count_collisions(vent_lines: &[VentLine]) -> Result<usize> {
     Ok(pos_count.values().filter(|count| **count >= 2).count())
 }
 
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    let mut vent_lines = parse_input(input)?;
-    vent_lines.drain_filter(|vl| vl.is_diagnonal());
-    count_collisions(&vent_lines)
+pub fn solve_part_1(vent_lines: &[VentLine]) -> anyhow::Result<Answer> {
+    count_collisions(vent_lines.iter().filter(|vl| !vl.is_diagnonal())).map(Answer::from)
+}
+
+pub fn solve_part_2(vent_lines: &[VentLine]) -> anyhow::Result<Answer> {
+    count_collisions(vent_lines).map(Answer::from)
+}
+
+/// Repl commands: `show line <n>` and `show pos <x,y>`
+pub fn explore(vent_lines: &[VentLine], _step: usize, command: &[&str]) -> Option<Result<String>> {
+    match command {
+        ["show", "line", n] => Some(n.parse::<usize>().map_err(|e| anyhow!(e)).and_then(|n| {
+            let vent_line = n
+                .checked_sub(1)
+                .and_then(|i| vent_lines.get(i))
+                .ok_or(anyhow!("Only lines 1 to {} exist!", vent_lines.len()))?;
+            let kind = if vent_line.is_diagnonal() {
+                "diagonal"
+            } else {
+                "ortogonal"
+            };
+            let positions = vent_line
+                .iter_positions()?
+                .map(|pos| pos.to_string())
+                .collect::<Vec<_>>();
+            let shown = positions.len().min(10);
+            Ok(format!(
+                "{} ({}, {} positions)\n{}{}",
+                vent_line,
+                kind,
+                positions.len(),
+                positions[..shown].join(" "),
+                if shown < positions.len() { " ..." } else { "" }
+            ))
+        })),
+        ["show", "pos", pos] => Some((|| {
+            let (x, y) = pos
+                .split_once(',')
+                .ok_or(anyhow!("Expected a position like 3,4"))?;
+            let pos = Pos {
+                x: x.parse()?,
+                y: y.parse()?,
+            };
+            let mut text = format!("Lines crossing {}:", pos);
+            for (i, vent_line) in vent_lines.iter().enumerate() {
+                if vent_line.iter_positions()?.any(|p| p == pos) {
+                    text += &format!("\n{}: {}", i + 1, vent_line);
+                }
+            }
+            Ok(text)
+        })()),
+        _ => None,
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<VentLine>;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 5;
+    const TITLE: &'static str = "Hydrothermal Venture";
+
+    fn parse(input: &str) -> anyhow::Result<Vec<VentLine>> {
+        parse_input(input)
+    }
+
+    fn part1(vent_lines: &Vec<VentLine>) -> anyhow::Result<Answer> {
+        solve_part_1(vent_lines)
+    }
+
+    fn part2(vent_lines: &Vec<VentLine>) -> anyhow::Result<Answer> {
+        solve_part_2(vent_lines)
+    }
+
+    fn explore(
+        vent_lines: &Vec<VentLine>,
+        step: usize,
+        command: &[&str],
+    ) -> Option<Result<String>> {
+        explore(vent_lines, step, command)
+    }
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    count_collisions(&parse_input(input)?)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    #[test]
+    fn part_1_example() {
+        let vent_lines = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&vent_lines).unwrap().to_string(), "5");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let vent_lines = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&vent_lines).unwrap().to_string(), "12");
+    }
+
+    #[test]
+    fn diagonal_positions() {
+        let vent_line: VentLine = "9,7 -> 7,9".parse().unwrap();
+        assert!(vent_line.is_diagnonal());
+        let positions = vent_line
+            .iter_positions()
+            .unwrap()
+            .map(|pos| pos.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(positions, ["9,7", "8,8", "7,9"]);
+    }
+
+    #[test]
+    fn count_collisions_of_crossing_lines() {
+        let vent_lines = parse_input("0,0 -> 2,2\n0,2 -> 2,0\n1,0 -> 1,2\n").unwrap();
+        assert_eq!(count_collisions(&vent_lines).unwrap(), 1);
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = parse_input("0,9 -> 5,9\n\n9,4 -> 3,y\n").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (3, 10));
+    }
+
+    #[test]
+    fn invalid_angle() {
+        assert!("0,0 -> 1,2".parse::<VentLine>().is_err());
+    }
 }
@@ -1,12 +1,28 @@
-pub fn simulate(input: &str, days: usize) -> anyhow::Result<u64> {
-    // Index: Days remaining
-    // Value: Amount of lanternfish
+use crate::answer::Answer;
+use crate::input;
+use crate::solution::Solution;
+
+/// Index: Days remaining
+/// Value: Amount of lanternfish
+pub type FishPerDaysRemain = [u64; 9];
+
+pub fn parse_input(input: &str) -> anyhow::Result<FishPerDaysRemain> {
     let mut fish_per_days_remain = [0u64; 9];
+    let line = input::first_line(input).ok_or(anyhow!("No fish found!"))?;
 
     // Add initial fish
-    for days in input.trim().split(",").map(|word| word.parse::<usize>()) {
-        fish_per_days_remain[days?] += 1;
+    for word in line.text.split(',').map(str::trim) {
+        let days: usize = line.parse(word)?;
+        if days >= fish_per_days_remain.len() {
+            Err(line.error(word, format!("Invalid amount of days remaining: {}", days)))?;
+        }
+        fish_per_days_remain[days] += 1;
     }
+    Ok(fish_per_days_remain)
+}
+
+pub fn simulate(initial_fish: &FishPerDaysRemain, days: usize) -> anyhow::Result<u64> {
+    let mut fish_per_days_remain = *initial_fish;
 
     // Cycle all the simulated days
     for _ in 0..days {
@@ -27,10 +43,72 @@ pub fn simulate(input: &str, days: usize) -> anyhow::Result<u64> {
     Ok(fish_per_days_remain.iter().sum::<u64>())
 }
 
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    simulate(input, 80)
+pub fn solve_part_1(initial_fish: &FishPerDaysRemain) -> anyhow::Result<Answer> {
+    simulate(initial_fish, 80).map(Answer::from)
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    simulate(input, 256)
+pub fn solve_part_2(initial_fish: &FishPerDaysRemain) -> anyhow::Result<Answer> {
+    simulate(initial_fish, 256).map(Answer::from)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = FishPerDaysRemain;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 6;
+    const TITLE: &'static str = "Lanternfish";
+
+    fn parse(input: &str) -> anyhow::Result<FishPerDaysRemain> {
+        parse_input(input)
+    }
+
+    fn part1(initial_fish: &FishPerDaysRemain) -> anyhow::Result<Answer> {
+        solve_part_1(initial_fish)
+    }
+
+    fn part2(initial_fish: &FishPerDaysRemain) -> anyhow::Result<Answer> {
+        solve_part_2(initial_fish)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    const EXAMPLE: &str = "3,4,3,1,2\n";
+
+    #[test]
+    fn parse_list() {
+        assert_eq!(
+            parse_input(" 3, 4,3 ,1,2\n").unwrap(),
+            parse_input(EXAMPLE).unwrap()
+        );
+        let error = parse_input("3,9,1").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column, error.len), (1, 3, 1));
+    }
+
+    #[test]
+    fn simulate_example() {
+        let initial_fish = parse_input(EXAMPLE).unwrap();
+        assert_eq!(simulate(&initial_fish, 18).unwrap(), 26);
+    }
+
+    #[test]
+    fn part_1_example() {
+        let initial_fish = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&initial_fish).unwrap().to_string(), "5934");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let initial_fish = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            solve_part_2(&initial_fish).unwrap().to_string(),
+            "26984457539"
+        );
+    }
 }
@@ -1,26 +1,82 @@
-fn min_fuel<F>(input: &str, fuel_calc: F) -> anyhow::Result<isize>
+use crate::answer::Answer;
+use crate::input;
+use crate::solution::Solution;
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<isize>> {
+    let line = input::first_line(input).ok_or(anyhow!("No positions found!"))?;
+    Ok(line.numbers(',')?)
+}
+
+pub fn min_fuel<F>(positions: &[isize], fuel_calc: F) -> anyhow::Result<isize>
 where
     F: Fn(isize) -> isize,
 {
-    let positions = input
-        .trim()
-        .split(',')
-        .map(|word| word.parse())
-        .collect::<Result<Vec<isize>, _>>()?;
-    ensure!(positions.len() > 0, "No positions found!");
-
+    let min = *positions
+        .iter()
+        .min()
+        .ok_or(anyhow!("No positions found!"))?;
+    let max = *positions
+        .iter()
+        .max()
+        .ok_or(anyhow!("No positions found!"))?;
     // Iter from min to incl. max horizontal postition and find lowest
     // sum of all fuel calculations to that point.
-    (*positions.iter().min().unwrap()..=*positions.iter().max().unwrap())
+    (min..=max)
         .map(|t| positions.iter().map(|p| fuel_calc((t - *p).abs())).sum())
         .min()
         .ok_or(anyhow!("No minimum fuel found!"))
 }
 
-pub fn solve_part_1(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    min_fuel(input, |distance| distance)
+pub fn solve_part_1(positions: &[isize]) -> anyhow::Result<Answer> {
+    min_fuel(positions, |distance| distance).map(Answer::from)
+}
+
+pub fn solve_part_2(positions: &[isize]) -> anyhow::Result<Answer> {
+    min_fuel(positions, |distance| distance * (distance + 1) / 2).map(Answer::from)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<isize>;
+
+    const YEAR: usize = 2021;
+    const DAY: usize = 7;
+    const TITLE: &'static str = "The Treachery of Whales";
+
+    fn parse(input: &str) -> anyhow::Result<Vec<isize>> {
+        parse_input(input)
+    }
+
+    fn part1(positions: &Vec<isize>) -> anyhow::Result<Answer> {
+        solve_part_1(positions)
+    }
+
+    fn part2(positions: &Vec<isize>) -> anyhow::Result<Answer> {
+        solve_part_2(positions)
+    }
 }
 
-pub fn solve_part_2(input: &str) -> anyhow::Result<impl std::fmt::Display> {
-    min_fuel(input, |distance| distance * (distance + 1) / 2)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14\n";
+
+    #[test]
+    fn part_1_example() {
+        let positions = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&positions).unwrap().to_string(), "37");
+    }
+
+    #[test]
+    fn part_2_example() {
+        let positions = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&positions).unwrap().to_string(), "168");
+    }
+
+    #[test]
+    fn no_positions() {
+        assert!(min_fuel(&[], |distance| distance).is_err());
+    }
 }
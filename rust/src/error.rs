@@ -0,0 +1,296 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr, sync::Arc};
+
+use crate::solution::{self, FIRST_YEAR};
+
+/// Failure to parse a puzzle input. Points at the offending text when displayed:
+///
+/// ```text
+/// Invalid number "x": invalid digit found in string
+///  --> ../input/day5.txt:3:8
+///   |
+/// 3 | 9,4 -> x,4
+///   |        ^
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    /// Input file, if known
+    pub path: Option<PathBuf>,
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+    /// The whole line containing the error
+    pub text: String,
+    /// Amount of characters that are wrong (at least one gets marked)
+    pub len: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Error about `span`, which should be a slice of `line`. Otherwise the whole line
+    /// gets marked. The line index starts at 0 like returned by `enumerate()`.
+    pub fn new(line_index: usize, line: &str, span: &str, message: impl Display) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| offset + span.len() <= line.len());
+        let (column, len) = match offset {
+            Some(offset) => (line[..offset].chars().count() + 1, span.chars().count()),
+            None => (1, line.chars().count()),
+        };
+        Self {
+            path: None,
+            line: line_index + 1,
+            column,
+            text: line.to_owned(),
+            len,
+            message: message.to_string(),
+        }
+    }
+
+    /// Error about a whole line (ignoring surrounding whitespace)
+    pub fn line(line_index: usize, line: &str, message: impl Display) -> Self {
+        Self::new(line_index, line, line.trim(), message)
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+/// Parse a word (slice of `line`), reporting its position if that fails
+pub fn parse_word<T>(line_index: usize, line: &str, word: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    word.parse().map_err(|e| {
+        ParseError::new(
+            line_index,
+            line,
+            word,
+            format!("Invalid number {:?}: {}", word, e),
+        )
+    })
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        let gutter = self.line.to_string().len();
+        match &self.path {
+            Some(path) => writeln!(
+                f,
+                "{:gutter$}--> {}:{}:{}",
+                "",
+                path.display(),
+                self.line,
+                self.column,
+                gutter = gutter
+            )?,
+            None => writeln!(
+                f,
+                "{:gutter$}--> line {}, column {}",
+                "",
+                self.line,
+                self.column,
+                gutter = gutter
+            )?,
+        }
+        writeln!(f, "{:gutter$} |", "", gutter = gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text.trim_end())?;
+        write!(
+            f,
+            "{:gutter$} | {:column$}{}",
+            "",
+            "",
+            "^".repeat(self.len.max(1)),
+            gutter = gutter,
+            column = self.column - 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A day (or year) without a puzzle
+#[derive(Debug)]
+pub struct InvalidDay {
+    pub year: usize,
+    pub day: usize,
+}
+
+impl Display for InvalidDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days = solution::puzzle_days(self.year);
+        if self.year < FIRST_YEAR {
+            write!(
+                f,
+                "Invalid year {} Advent of Code started in {}!",
+                self.year, FIRST_YEAR
+            )
+        } else {
+            write!(
+                f,
+                "Invalid day \"{}\" Only {}-{} exist in {}!",
+                self.day,
+                days.start(),
+                days.end(),
+                self.year
+            )
+        }
+    }
+}
+
+impl std::error::Error for InvalidDay {}
+
+/// A part other than 1 and 2
+#[derive(Debug)]
+pub struct InvalidPart(pub usize);
+
+impl Display for InvalidPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid part \"{}\" Only 1 and 2 exist!", self.0)
+    }
+}
+
+impl std::error::Error for InvalidPart {}
+
+/// A puzzle without a solution in the registry
+#[derive(Debug)]
+pub struct NotImplemented {
+    pub year: usize,
+    /// None if nothing of the year is implemented
+    pub day: Option<usize>,
+    /// Implemented days of the year, or implemented years if there are none
+    pub implemented: Vec<usize>,
+}
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let implemented = solution::format_numbers(&self.implemented);
+        match self.day {
+            Some(day) => write!(
+                f,
+                "Day {} of {} is not implemented yet! Implemented days: {}",
+                day, self.year, implemented
+            ),
+            None => write!(
+                f,
+                "Nothing implemented for {} Implemented years: {}",
+                self.year, implemented
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// An input file that couldn't be read or parsed. Cheap to clone, so each part of a day
+/// can fail with it while keeping the cause.
+#[derive(Clone, Debug)]
+pub struct InputError(Arc<anyhow::Error>);
+
+impl InputError {
+    pub fn new(error: anyhow::Error) -> Self {
+        InputError(Arc::new(error))
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Why a run failed, told by the exit code of the binary.
+/// Ordered by precedence, when several parts fail the greatest failure is reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Failure {
+    /// A solver returned an error or a wrong answer. Also used for anything unexpected.
+    SolverFailed = 1,
+    /// An invalid day, year or part. The same code as clap's own usage errors.
+    Usage = 2,
+    /// An input file that can't be read or parsed
+    BadInput = 3,
+    /// The day has a puzzle, but no solution yet
+    NotImplemented = 4,
+}
+
+impl Failure {
+    pub fn of(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if cause.is::<NotImplemented>() {
+                return Failure::NotImplemented;
+            }
+            if cause.is::<InvalidDay>() || cause.is::<InvalidPart>() {
+                return Failure::Usage;
+            }
+            if cause.is::<InputError>() || cause.is::<ParseError>() {
+                return Failure::BadInput;
+            }
+        }
+        Failure::SolverFailed
+    }
+
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_span() {
+        let line = "  9,4 -> x,4";
+        let error = ParseError::new(2, line, &line[9..10], "Invalid number");
+        assert_eq!((error.line, error.column, error.len), (3, 10, 1));
+    }
+
+    #[test]
+    fn foreign_span_marks_whole_line() {
+        let error = ParseError::new(0, "abc", "x", "Invalid");
+        assert_eq!((error.column, error.len), (1, 3));
+    }
+
+    #[test]
+    fn failure_kinds() {
+        let error = solution::lookup(2021, 25).err().unwrap();
+        assert_eq!(Failure::of(&error), Failure::NotImplemented);
+        let error = solution::lookup(2021, 26).err().unwrap();
+        assert_eq!(Failure::of(&error), Failure::Usage);
+        assert_eq!(Failure::of(&InvalidPart(3).into()), Failure::Usage);
+        let error = anyhow::Error::new(ParseError::line(0, "x", "Invalid")).context("Parsing");
+        assert_eq!(Failure::of(&error), Failure::BadInput);
+        let error = anyhow!("Reading input file day1.txt");
+        assert_eq!(
+            Failure::of(&InputError::new(error).into()),
+            Failure::BadInput
+        );
+        assert_eq!(Failure::of(&anyhow!("Solving")), Failure::SolverFailed);
+    }
+
+    #[test]
+    fn render_snippet() {
+        let line = "9,4 -> x,4";
+        let error = parse_word::<i16>(2, line, &line[7..8])
+            .unwrap_err()
+            .with_path("day5.txt");
+        assert_eq!(
+            error.to_string(),
+            "Invalid number \"x\": invalid digit found in string\n \
+             --> day5.txt:3:8\n  \
+             |\n\
+             3 | 9,4 -> x,4\n  \
+             |        ^"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    process::Command,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::bench::{BenchResult, Stats};
+use crate::runner::PartResult;
+
+/// Where a run happened: The checked out code and the machine running it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Environment {
+    /// Unix timestamp of the run
+    pub time: u64,
+    /// Output of `git describe --always --dirty`, if available
+    pub commit: Option<String>,
+    pub host: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+}
+
+impl Environment {
+    pub fn detect() -> Self {
+        Self {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            commit: git_commit(),
+            host: fs::read_to_string("/proc/sys/kernel/hostname")
+                .ok()
+                .or_else(|| env::var("HOSTNAME").ok())
+                .or_else(|| env::var("COMPUTERNAME").ok())
+                .map_or_else(|| "unknown".to_owned(), |host| host.trim().to_owned()),
+            os: env::consts::OS.to_owned(),
+            arch: env::consts::ARCH.to_owned(),
+            cpus: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_owned()).filter(|c| !c.is_empty())
+}
+
+/// A solved part, one line of the history file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    /// Amount of measured runs, 1 unless benchmarked
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+    #[serde(flatten)]
+    pub environment: Environment,
+}
+
+impl Entry {
+    /// Entry of a single run. None if the part failed.
+    pub fn from_result(result: &PartResult, environment: &Environment) -> Option<Self> {
+        Some(Self {
+            day: result.day,
+            part: result.part,
+            answer: result.answer.as_ref().ok()?.to_string(),
+            runs: 1,
+            parse: Stats::new(vec![result.parse_duration]),
+            solve: Stats::new(vec![result.duration]),
+            environment: environment.clone(),
+        })
+    }
+
+    pub fn from_bench(result: &BenchResult, environment: &Environment) -> Self {
+        Self {
+            day: result.day,
+            part: result.part,
+            answer: result.answer.to_string(),
+            runs: result.runs,
+            parse: result.parse,
+            solve: result.solve,
+            environment: environment.clone(),
+        }
+    }
+}
+
+/// Add entries to the end of the history file, one json object per line
+pub fn append(path: &Path, entries: &[Entry]) -> Result<()> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines += &serde_json::to_string(entry)?;
+        lines.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .with_context(|| format!("Writing history file {}", path.display()))
+}
+
+/// All entries of the history file, oldest first. No file means no history yet.
+pub fn load(path: &Path) -> Result<Vec<Entry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Reading history file {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Parsing line {} of {}", i + 1, path.display()))
+        })
+        .collect()
+}
+
+/// How an entry differs from the previous one of the same day and part
+#[derive(Debug, PartialEq)]
+struct Change<'a> {
+    /// Relative change of the median solve time
+    solve: Option<f64>,
+    /// The previous answer if it was different
+    answer: Option<&'a str>,
+}
+
+fn changes<'a>(entries: &[&'a Entry]) -> Vec<Change<'a>> {
+    let mut previous: Option<&Entry> = None;
+    entries
+        .iter()
+        .map(|entry| {
+            let change = match previous {
+                None => Change {
+                    solve: None,
+                    answer: None,
+                },
+                Some(previous) => Change {
+                    solve: Some(
+                        entry.solve.median.as_secs_f64() / previous.solve.median.as_secs_f64()
+                            - 1.0,
+                    )
+                    .filter(|change| change.is_finite()),
+                    answer: Some(previous.answer.as_str()).filter(|answer| *answer != entry.answer),
+                },
+            };
+            previous = Some(entry);
+            change
+        })
+        .collect()
+}
+
+/// Print how the solve times and answers of each day and part developed over time
+pub fn print_history(entries: &[Entry], day: Option<usize>, part: Option<usize>) {
+    let mut groups = BTreeMap::<_, Vec<_>>::new();
+    for entry in entries
+        .iter()
+        .filter(|e| day.is_none_or(|day| e.day == day))
+        .filter(|e| part.is_none_or(|part| e.part == part))
+    {
+        groups
+            .entry((entry.day, entry.part))
+            .or_default()
+            .push(entry);
+    }
+    if groups.is_empty() {
+        println!("No history yet");
+        return;
+    }
+
+    for ((day, part), entries) in groups {
+        println!("Day {} part {}", day, part);
+        println!(
+            "  {:16}  {:16}  {:12}  {:>6}  {:>12}  {:>8}  Answer",
+            "Time (UTC)", "Commit", "Host", "Runs", "Solve", "Change"
+        );
+        for (entry, change) in entries.iter().zip(changes(&entries)) {
+            let environment = &entry.environment;
+            println!(
+                "  {:16}  {:16}  {:12}  {:>6}  {:>12}  {:>8}  {}{}",
+                format_time(environment.time),
+                environment.commit.as_deref().unwrap_or("-"),
+                environment.host,
+                entry.runs,
+                format!("{:?}", entry.solve.median),
+                change
+                    .solve
+                    .map(|change| format!("{:+.1}%", change * 100.0))
+                    .unwrap_or_default(),
+                entry.answer,
+                change
+                    .answer
+                    .map(|previous| format!("  CHANGED (was {})", previous))
+                    .unwrap_or_default()
+            );
+        }
+        println!();
+    }
+}
+
+/// Format a unix timestamp like "2021-12-05 06:00"
+fn format_time(time: u64) -> String {
+    let (days, seconds) = (time / 86400, time % 86400);
+    // Convert days since 1970-01-01 to a date (https://howardhinnant.github.io/date_algorithms.html)
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn entry(answer: &str, solve_millis: u64) -> Entry {
+        let stats = Stats::new(vec![Duration::from_millis(solve_millis)]);
+        Entry {
+            day: 5,
+            part: 1,
+            answer: answer.to_owned(),
+            runs: 1,
+            parse: stats,
+            solve: stats,
+            environment: Environment {
+                time: 0,
+                commit: None,
+                host: "test".to_owned(),
+                os: "linux".to_owned(),
+                arch: "x86_64".to_owned(),
+                cpus: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn detect_changes() {
+        let entries = [entry("5092", 10), entry("5092", 5), entry("5093", 5)];
+        let changes = changes(&entries.iter().collect::<Vec<_>>());
+        assert_eq!(
+            changes[0],
+            Change {
+                solve: None,
+                answer: None
+            }
+        );
+        assert_eq!(
+            changes[1],
+            Change {
+                solve: Some(-0.5),
+                answer: None
+            }
+        );
+        assert_eq!(
+            changes[2],
+            Change {
+                solve: Some(0.0),
+                answer: Some("5092")
+            }
+        );
+    }
+
+    #[test]
+    fn entry_roundtrip() {
+        let line = serde_json::to_string(&entry("5092", 3)).unwrap();
+        let parsed: Entry = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.solve.median, Duration::from_millis(3));
+        assert_eq!(parsed.environment.host, "test");
+    }
+
+    #[test]
+    fn format_timestamps() {
+        assert_eq!(format_time(0), "1970-01-01 00:00");
+        assert_eq!(format_time(1638684000), "2021-12-05 06:00");
+        assert_eq!(format_time(1709164800), "2024-02-29 00:00");
+    }
+}
@@ -0,0 +1,131 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use crate::error::{parse_word, ParseError};
+
+/// A non-empty line of the puzzle input
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// Line index, starting at 0
+    pub index: usize,
+    /// The line as it is in the input
+    pub raw: &'a str,
+    /// The line without surrounding whitespace (including a trailing "\r")
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(index: usize, raw: &'a str) -> Self {
+        Self {
+            index,
+            raw,
+            text: raw.trim(),
+        }
+    }
+
+    /// Error about a part of this line (or the whole line if the span is no slice of it)
+    pub fn error(&self, span: &str, message: impl Display) -> ParseError {
+        ParseError::new(self.index, self.raw, span, message)
+    }
+
+    /// Parse a word that is a slice of this line
+    pub fn parse<T>(&self, word: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        parse_word(self.index, self.raw, word)
+    }
+
+    /// Parse the whole line as a list like "3,4,3,1,2"
+    pub fn numbers<T>(&self, delimiter: char) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split(delimiter)
+            .map(|word| self.parse(word.trim()))
+            .collect()
+    }
+}
+
+/// Convert Windows line endings ("\r\n") to "\n"
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+fn all_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .split('\n')
+        .enumerate()
+        .map(|(index, raw)| Line::new(index, raw))
+}
+
+/// All lines that aren't empty or only whitespace
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    all_lines(input).filter(|line| !line.text.is_empty())
+}
+
+/// The first line that isn't empty, for inputs that are a single line
+pub fn first_line(input: &str) -> Option<Line<'_>> {
+    lines(input).next()
+}
+
+/// Groups of lines separated by one or more empty lines
+pub fn blocks(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks = Vec::new();
+    let mut block = Vec::new();
+    for line in all_lines(input) {
+        if !line.text.is_empty() {
+            block.push(line);
+        } else if !block.is_empty() {
+            blocks.push(std::mem::take(&mut block));
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_keep_their_index() {
+        let lines = lines("1\r\n\r\n  2 \r\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[1].index, lines[1].text), (2, "2"));
+    }
+
+    #[test]
+    fn blocks_split_at_empty_lines() {
+        let blocks = blocks("a\nb\n\n\nc\n");
+        let texts = blocks
+            .iter()
+            .map(|block| block.iter().map(|line| line.text).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, [vec!["a", "b"], vec!["c"]]);
+        assert_eq!(blocks[1][0].index, 4);
+    }
+
+    #[test]
+    fn delimited_numbers() {
+        let line = first_line("\n16,1,2\n").unwrap();
+        assert_eq!(line.numbers::<u8>(',').unwrap(), [16, 1, 2]);
+
+        let error = first_line("1,x").unwrap().numbers::<u8>(',').unwrap_err();
+        assert_eq!(error.column, 3);
+    }
+
+    #[test]
+    fn normalize_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb\n");
+        assert!(matches!(normalize("a\nb"), Cow::Borrowed(_)));
+    }
+}
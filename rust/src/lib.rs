@@ -0,0 +1,34 @@
+//! Advent of Code 2021 solutions. Each `dayN` module exposes its parser, domain types and
+//! `solve_part_1`/`solve_part_2`; [`solution::REGISTRY`] lists them all for the runner.
+
+#![cfg_attr(feature = "nightly", feature(iter_partition_in_place))]
+
+#[macro_use]
+extern crate anyhow;
+#[macro_use]
+extern crate log;
+
+#[macro_use]
+extern crate if_chain;
+
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod compare;
+pub mod config;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod error;
+pub mod history;
+pub mod input;
+pub mod output;
+pub mod runner;
+pub mod solution;
+pub mod submissions;
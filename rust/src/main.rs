@@ -1,105 +1,835 @@
-#![feature(drain_filter)]
-
 #[macro_use]
 extern crate anyhow;
 #[macro_use]
 extern crate log;
 
-#[macro_use]
-extern crate if_chain;
+use adventofcode_2021::alloc::{self, CountingAllocator};
+use adventofcode_2021::answer::Answer;
+use adventofcode_2021::answers::{Answers, Verdict};
+use adventofcode_2021::bench::{self, BenchConfig};
+use adventofcode_2021::client::{self, Client};
+use adventofcode_2021::compare::{self, Agreement, ExternalSolver};
+use adventofcode_2021::config::{self, Config};
+use adventofcode_2021::error::Failure;
+use adventofcode_2021::history::{self, Entry, Environment};
+use adventofcode_2021::output::{self, Format, Record};
+use adventofcode_2021::solution::{InputFiles, DEFAULT_INPUT_PATTERN, DEFAULT_YEAR};
+use adventofcode_2021::submissions::{Attempt, Outcome, Submissions};
+use adventofcode_2021::{runner, solution};
+use anyhow::Result;
+use clap::{crate_authors, crate_version, AppSettings, Parser, Subcommand};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::exit,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+mod repl;
+mod scaffold;
+mod watch;
 
-use anyhow::{Context, Result};
-use clap::{crate_authors, crate_version, Parser};
-use std::{env, fs, path::PathBuf, process::exit, time::Instant};
+/// Only counts once enabled by --profile-alloc
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
+/// The input directory of this repository, so it's found regardless of the current directory
+const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../input");
 
 #[derive(Debug, Parser)]
-#[clap(version = crate_version!(), author = crate_authors!())]
+#[clap(
+    version = crate_version!(),
+    author = crate_authors!(),
+    setting = AppSettings::SubcommandsNegateReqs
+)]
 struct Opts {
-    #[clap(long, short = 'f', about = "Specifiy another input file to use")]
-    input_file: Option<PathBuf>,
+    #[clap(
+        long,
+        short = 'f',
+        multiple_occurrences = true,
+        about = "Specifiy other input files to use (\"-\" for stdin)"
+    )]
+    input_file: Vec<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_CONFIG",
+        about = "Config file with defaults for these options (defaults to aoc.toml in the current directory or the repository)"
+    )]
+    config: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_YEAR",
+        about = "Year of the puzzles (defaults to 2021)"
+    )]
+    year: Option<usize>,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_INPUT_DIR",
+        about = "Directory containing a directory with the input files of each year (defaults to the input directory of the repository)"
+    )]
+    input_dir: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        about = "File names of the inputs, with {day} or {day:02} as placeholder (defaults to day{day}.txt)"
+    )]
+    input_pattern: Option<String>,
+    #[clap(
+        long,
+        global = true,
+        about = "Compare the answers against the answers file"
+    )]
+    check: bool,
+    #[clap(
+        long,
+        global = true,
+        about = "Answers file used by --check (defaults to answers.toml in the input directory)"
+    )]
+    answers_file: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        arg_enum,
+        about = "How to print the results to stdout (defaults to text)"
+    )]
+    format: Option<Format>,
+    #[clap(
+        long,
+        global = true,
+        about = "Count allocations, allocated bytes and peak heap use of every solver"
+    )]
+    profile_alloc: bool,
+    #[clap(
+        long,
+        global = true,
+        about = "File to record answers and timings in (defaults to history.jsonl in the input directory)"
+    )]
+    history_file: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        about = "Don't record this run in the history file"
+    )]
+    no_history: bool,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_BASE_URL",
+        default_value = client::DEFAULT_BASE_URL,
+        about = "Website to download inputs from"
+    )]
+    base_url: String,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_SESSION",
+        hide_env_values = true,
+        about = "Session cookie of the website (instead of reading it from the session file)"
+    )]
+    session: Option<String>,
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_SESSION_FILE",
+        about = "File containing the session cookie (defaults to ~/.config/adventofcode/session)"
+    )]
+    session_file: Option<PathBuf>,
 
-    #[clap(about = "What day to solve")]
-    day: usize,
-    #[clap(about = "What part to solve")]
-    part: usize,
+    #[clap(long, about = "List the implemented days and their titles")]
+    list: bool,
+
+    #[clap(about = "What day to solve", required_unless_present = "list")]
+    day: Option<usize>,
+    #[clap(about = "What part to solve: 1, 2 or both (default)")]
+    part: Option<Parts>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Loaded from the config file
+    #[clap(skip)]
+    settings: Config,
 }
 
-fn main() -> Result<()> {
-    // Parse cli
-    let opts = Opts::parse();
+impl Opts {
+    fn inputs(&self) -> InputFiles {
+        InputFiles {
+            dir: (self.input_dir.clone())
+                .or_else(|| self.settings.input_dir.clone())
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR)),
+            pattern: (self.input_pattern.clone())
+                .or_else(|| self.settings.input_pattern.clone())
+                .unwrap_or_else(|| DEFAULT_INPUT_PATTERN.to_owned()),
+        }
+    }
+
+    fn format(&self) -> Format {
+        self.format.or(self.settings.format).unwrap_or(Format::Text)
+    }
+
+    fn year(&self) -> usize {
+        self.year.or(self.settings.year).unwrap_or(DEFAULT_YEAR)
+    }
+}
+
+/// Parts to solve. Parsed from "1", "2" or "both".
+#[derive(Clone, Debug)]
+struct Parts(Vec<usize>);
+
+impl FromStr for Parts {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Parts(vec![1])),
+            "2" => Ok(Parts(vec![2])),
+            "both" => Ok(Parts(vec![1, 2])),
+            _ => bail!("Invalid part \"{}\" Only 1, 2 and both are allowed!", s),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[clap(about = "Solve both parts of every implemented day")]
+    All {
+        #[clap(
+            long,
+            short = 'j',
+            default_value = "1",
+            about = "Solve days and parts on this many threads at once (0 for one per CPU)"
+        )]
+        jobs: usize,
+    },
+    #[clap(about = "Measure parse and solve times by running solvers repeatedly")]
+    Bench {
+        #[clap(about = "Only benchmark this day")]
+        day: Option<usize>,
+        #[clap(about = "Only benchmark this part")]
+        part: Option<usize>,
+        #[clap(long, about = "Unmeasured runs beforehand (defaults to 3)")]
+        warmup: Option<usize>,
+        #[clap(long, short = 'n', about = "Measured runs (defaults to 100)")]
+        runs: Option<usize>,
+        #[clap(
+            long,
+            about = "Run for this many seconds per solver instead of a fixed amount of runs"
+        )]
+        budget: Option<f64>,
+    },
+    #[clap(about = "Generate and register a module for a new day")]
+    New {
+        #[clap(about = "Day to generate")]
+        day: usize,
+        #[clap(long, default_value = "", about = "Title of the puzzle")]
+        title: String,
+    },
+    #[clap(about = "Solve again whenever the input files change")]
+    Watch {
+        #[clap(about = "Day to solve")]
+        day: usize,
+        #[clap(about = "What part to solve: 1, 2 or both (default)")]
+        part: Option<Parts>,
+        #[clap(long, short = 'e', about = "Also solve dayN.example.txt")]
+        example: bool,
+        #[clap(
+            long,
+            short = 'f',
+            multiple_occurrences = true,
+            about = "Watch other input files instead of dayN.txt"
+        )]
+        input_file: Vec<PathBuf>,
+        #[clap(
+            long,
+            default_value = "500",
+            about = "Milliseconds between checking for changes"
+        )]
+        interval: u64,
+    },
+    #[clap(about = "Download the input of a day unless it's already in the input directory")]
+    Fetch {
+        #[clap(about = "Day to download")]
+        day: usize,
+    },
+    #[clap(about = "Solve a part and submit the answer to the website")]
+    Submit {
+        #[clap(about = "Day to solve")]
+        day: usize,
+        #[clap(about = "Part to solve")]
+        part: usize,
+        #[clap(long, about = "Submit even if the answer is outside the known bounds")]
+        force: bool,
+    },
+    #[clap(about = "Check that another implementation gets the same answers")]
+    Compare {
+        #[clap(about = "Only compare this day")]
+        day: Option<usize>,
+        #[clap(about = "Only compare this part")]
+        part: Option<usize>,
+        #[clap(
+            long,
+            default_value = compare::GO_COMMAND,
+            about = "Command solving a part, with {day}, {part} and {input} as placeholders"
+        )]
+        command: String,
+        #[clap(
+            long,
+            default_value = compare::GO_ANSWER_PREFIX,
+            about = "Text in front of the answer in the output of the command (empty for the last line)"
+        )]
+        answer_prefix: String,
+        #[clap(
+            long,
+            short = 'f',
+            requires = "day",
+            about = "Input file to use instead of dayN.txt"
+        )]
+        input_file: Option<PathBuf>,
+    },
+    #[clap(about = "Parse the input of a day once and explore it interactively")]
+    Repl {
+        #[clap(about = "Day to explore")]
+        day: usize,
+        #[clap(long, short = 'f', about = "Input file to use instead of dayN.txt")]
+        input_file: Option<PathBuf>,
+    },
+    #[clap(about = "Show how answers and solve times changed over the recorded runs")]
+    History {
+        #[clap(about = "Only show this day")]
+        day: Option<usize>,
+        #[clap(about = "Only show this part")]
+        part: Option<usize>,
+    },
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("Error: {:?}", e);
+        exit(Failure::of(&e).code());
+    }
+}
+
+fn run() -> Result<()> {
+    // Parse cli and fill in the rest from the config file
+    let mut opts = Opts::parse();
+    let config_file = opts.config.clone().or_else(config::find);
+    if let Some(config_file) = &config_file {
+        opts.settings = Config::load(config_file)?;
+    }
 
     // Init logging (default to info)
     if env::var("RUST_LOG").is_err() {
-        env::set_var("RUST_LOG", "INFO");
+        let level = opts.settings.log_level.as_deref().unwrap_or("INFO");
+        env::set_var("RUST_LOG", level);
     }
     env_logger::init();
+    if let Some(config_file) = &config_file {
+        debug!("Using config file {}", config_file.display());
+    }
+    let inputs = opts.inputs();
+    let format = opts.format();
+    let year = opts.year();
+
+    if opts.profile_alloc {
+        alloc::enable();
+    }
+
+    let answers = if opts.check {
+        let answers_file = (opts.answers_file.clone())
+            .unwrap_or_else(|| inputs.year_dir(year).join("answers.toml"));
+        Some(Answers::load(&answers_file)?)
+    } else {
+        None
+    };
+
+    match opts.command {
+        Some(Command::All { jobs }) => {
+            let jobs = if jobs == 0 {
+                thread::available_parallelism().map_or(1, |n| n.get())
+            } else {
+                jobs
+            };
+            // Allocations are counted globally, so other threads would be counted as well
+            let jobs = if opts.profile_alloc && jobs > 1 {
+                warn!("Solving on a single thread to profile allocations");
+                1
+            } else {
+                jobs
+            };
+            let start = Instant::now();
+            let results = runner::run_all(&solution::solutions(year)?, &inputs, jobs);
+            debug!(
+                "Solved everything in {:?} on {} threads",
+                start.elapsed(),
+                jobs
+            );
+            let environment = Environment::detect();
+            record_history(
+                &opts,
+                results
+                    .iter()
+                    .filter_map(|result| Entry::from_result(result, &environment))
+                    .collect(),
+            );
+            if format == Format::Text {
+                runner::print_summary(&results, answers.as_ref(), jobs);
+            } else {
+                let records = results
+                    .iter()
+                    .map(|result| {
+                        let input = solution::find(year, result.day)
+                            .map(|solution| solution.default_input_path(&inputs))
+                            .unwrap_or_default();
+                        Record::new(result, &input)
+                    })
+                    .collect::<Vec<_>>();
+                output::print_records(&records, format)?;
+            }
+            let failure = (results.iter())
+                .filter_map(|r| match (&r.answer, &answers) {
+                    (Err(e), _) => Some(Failure::of(e)),
+                    (Ok(answer), Some(answers)) => {
+                        matches!(answers.check(r.day, r.part, answer), Verdict::Wrong { .. })
+                            .then_some(Failure::SolverFailed)
+                    }
+                    (Ok(_), None) => None,
+                })
+                .max();
+            if let Some(failure) = failure {
+                exit(failure.code());
+            }
+            Ok(())
+        }
+        Some(Command::Bench {
+            day,
+            part,
+            warmup,
+            runs,
+            budget,
+        }) => {
+            let solutions = match day {
+                Some(day) => vec![solution::lookup(year, day)?],
+                None => solution::solutions(year)?,
+            };
+            let parts = match part {
+                Some(part) => {
+                    solution::check_part(part)?;
+                    vec![part]
+                }
+                None => vec![1, 2],
+            };
+            let settings = &opts.settings.bench;
+            let config = BenchConfig {
+                warmup: warmup.or(settings.warmup).unwrap_or(3),
+                runs: runs.or(settings.runs).unwrap_or(100),
+                budget: budget.or(settings.budget).map(Duration::from_secs_f64),
+            };
+            let (results, errors) = bench::bench_all(&solutions, &inputs, &parts, &config);
+            let environment = Environment::detect();
+            record_history(
+                &opts,
+                results
+                    .iter()
+                    .map(|result| Entry::from_bench(result, &environment))
+                    .collect(),
+            );
+            bench::print_results(&results, format)?;
+            if let Some(failure) = errors.iter().map(Failure::of).max() {
+                exit(failure.code());
+            }
+            Ok(())
+        }
+        Some(Command::New { day, title }) => {
+            ensure!(
+                year == DEFAULT_YEAR,
+                "Only days of {} can be generated, the modules are named after the day only",
+                DEFAULT_YEAR
+            );
+            solution::check_day(year, day)?;
+            ensure!(
+                solution::find(year, day).is_none(),
+                "Day {} is already implemented!",
+                day
+            );
+            let title = if title.is_empty() {
+                format!("Day {}", day)
+            } else {
+                title
+            };
+            scaffold::new_day(day, &title, Path::new(scaffold::SRC_DIR), &inputs)?;
+            info!("Rebuild to solve day {}", day);
+            Ok(())
+        }
+        Some(Command::Watch {
+            day,
+            part,
+            example,
+            input_file,
+            interval,
+        }) => {
+            let solution = solution::lookup(year, day)?;
+            let mut input_files = if input_file.is_empty() {
+                vec![solution.default_input_path(&inputs)]
+            } else {
+                input_file
+            };
+            if example {
+                input_files.push(inputs.example_path(year, day));
+            }
+            watch::watch(
+                solution,
+                &input_files,
+                &Path::new(scaffold::SRC_DIR).join(format!("day{}.rs", day)),
+                &part.map_or(vec![1, 2], |parts| parts.0),
+                Duration::from_millis(interval),
+            )
+        }
+        Some(Command::Fetch { day }) => {
+            solution::check_day(year, day)?;
+            // Only require a session token when there is something to download
+            if let Some(path) = client::cached_input(&inputs, year, day) {
+                info!("Input of day {} already exists at {}", day, path.display());
+            } else {
+                let path = client::fetch_input(&client(&opts)?, &inputs, day)?;
+                info!("Downloaded input of day {} to {}", day, path.display());
+            }
+            Ok(())
+        }
+        Some(Command::Submit { day, part, force }) => submit(&opts, day, part, force),
+        Some(Command::Compare {
+            day,
+            part,
+            command,
+            answer_prefix,
+            input_file,
+        }) => compare(
+            year,
+            &inputs,
+            day,
+            part,
+            &ExternalSolver::new(&command, &answer_prefix)?,
+            input_file,
+        ),
+        Some(Command::Repl { day, input_file }) => {
+            let solution = solution::lookup(year, day)?;
+            let input_file = input_file.unwrap_or_else(|| solution.default_input_path(&inputs));
+            repl::repl(solution, &input_file)
+        }
+        Some(Command::History { day, part }) => {
+            let entries = history::load(&history_file(&opts))?;
+            history::print_history(&entries, day, part);
+            Ok(())
+        }
+        None if opts.list => {
+            list();
+            Ok(())
+        }
+        None => solve(
+            opts.day.unwrap(),
+            &opts.part.clone().map_or(vec![1, 2], |parts| parts.0),
+            &inputs,
+            opts.input_file.clone(),
+            answers.as_ref(),
+            &opts,
+        ),
+    }
+}
+
+/// Print the registered days of every year
+fn list() {
+    for year in solution::years() {
+        println!("{}", year);
+        for solution in solution::solutions(year).unwrap_or_default() {
+            println!("  {:>3}  {}", solution.day(), solution.title());
+        }
+    }
+}
+
+/// Client for the website, using the session token from the cli, environment or session file
+fn client(opts: &Opts) -> Result<Client> {
+    let session = match (&opts.session, &opts.session_file) {
+        (Some(session), _) => session.clone(),
+        (None, Some(session_file)) => client::read_session(session_file)?,
+        (None, None) => {
+            let home = env::var_os("HOME").ok_or(anyhow!(
+                "No session token given! Use --session or --session-file."
+            ))?;
+            client::read_session(&Path::new(&home).join(".config/adventofcode/session"))?
+        }
+    };
+    Ok(Client::new(&opts.base_url, &session, opts.year()))
+}
+
+/// Solve with both implementations and print whether they agree.
+/// When comparing every day, only disagreement is an error, as the other implementation
+/// might not know every day, unless not a single part could be compared.
+/// A day asked for explicitly has to be solved by both.
+fn compare(
+    year: usize,
+    inputs: &InputFiles,
+    day: Option<usize>,
+    part: Option<usize>,
+    other: &ExternalSolver,
+    input_file: Option<PathBuf>,
+) -> Result<()> {
+    let solutions = match day {
+        Some(day) => vec![solution::lookup(year, day)?],
+        None => solution::solutions(year)?,
+    };
+    if let Some(part) = part {
+        solution::check_part(part)?;
+    }
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let explicit = day.is_some();
 
-    // Basic bound checks
-    if opts.day < 1 || opts.day > 31 {
-        error!("Invalid day \"{}\" Only 1-31 are allowed!", opts.day);
-        exit(1);
-    }
-    if opts.part != 1 && opts.part != 2 {
-        error!("Invalid part \"{}\" Only 1 and 2 are allowed!", opts.part);
-        exit(1);
-    }
-
-    let filename = opts
-        .input_file
-        .unwrap_or(PathBuf::from(format!("../input/day{}.txt", opts.day)));
-    let filecontent = fs::read_to_string(filename).context("Reading input file")?;
-
-    let start = Instant::now();
-    let solution: String = match opts.day {
-        1 => match opts.part {
-            1 => day1::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day1::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        2 => match opts.part {
-            1 => day2::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day2::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        3 => match opts.part {
-            1 => day3::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day3::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        4 => match opts.part {
-            1 => day4::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day4::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        5 => match opts.part {
-            1 => day5::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day5::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        6 => match opts.part {
-            1 => day6::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day6::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        7 => match opts.part {
-            1 => day7::solve_part_1(&filecontent).map(|v| v.to_string()),
-            2 => day7::solve_part_2(&filecontent).map(|v| v.to_string()),
-            _ => unreachable!(),
-        },
-        _ => {
-            unimplemented!()
-        }
-    }
-    .with_context(|| format!("Solving day {} part {}", opts.day, opts.part))?;
-
-    info!("Solved in {:?}: {}", start.elapsed(), solution);
+    println!(
+        "Day  Part  {:>16}  {:>16}  Result",
+        "Answer", "Other answer"
+    );
+    let mut failure = None;
+    let mut compared = 0;
+    let mut unchecked = 0;
+    let mut all_results = Vec::new();
+    for solution in solutions {
+        let input = (input_file.clone()).unwrap_or_else(|| solution.default_input_path(inputs));
+        let results = match runner::run_file(solution, &input, &parts) {
+            Ok(results) => results,
+            Err(e) => {
+                error!("{:#}", e);
+                if explicit {
+                    failure = failure.max(Some(Failure::of(&e)));
+                }
+                continue;
+            }
+        };
+        for result in results {
+            let theirs = other.solve(solution.day(), result.part, &input);
+            let show = |answer: Result<String, _>| answer.unwrap_or_else(|_| "ERROR".to_owned());
+            let verdict = match Agreement::of(&result.answer, &theirs) {
+                Agreement::Agree => {
+                    compared += 1;
+                    "agree".to_owned()
+                }
+                Agreement::Differ => {
+                    compared += 1;
+                    failure = failure.max(Some(Failure::SolverFailed));
+                    "DIFFERENT".to_owned()
+                }
+                Agreement::Failed => {
+                    if explicit {
+                        let ours = result.answer.as_ref().err().map(Failure::of);
+                        failure = failure.max(Some(ours.unwrap_or(Failure::SolverFailed)));
+                    }
+                    unchecked += 1;
+                    match (&result.answer, &theirs) {
+                        (Err(e), _) => format!("failed: {:#}", e),
+                        (_, Err(e)) => format!("other failed: {:#}", e),
+                        _ => unreachable!(),
+                    }
+                }
+            };
+            println!(
+                "{:>3}  {:>4}  {:>16}  {:>16}  {}",
+                result.day,
+                result.part,
+                show(result.answer.as_ref().map(|answer| answer.inline())),
+                show(theirs.as_ref().cloned()),
+                verdict
+            );
+            all_results.push(result);
+        }
+    }
+    runner::print_art(&all_results);
+    if unchecked > 0 {
+        warn!("{} parts couldn't be compared", unchecked);
+    }
+    if compared == 0 {
+        error!("Nothing could be compared, check that the command works");
+        failure = failure.max(Some(Failure::SolverFailed));
+    }
+    if let Some(failure) = failure {
+        exit(failure.code());
+    }
     Ok(())
 }
+
+fn history_file(opts: &Opts) -> PathBuf {
+    (opts.history_file.clone())
+        .unwrap_or_else(|| opts.inputs().year_dir(opts.year()).join("history.jsonl"))
+}
+
+/// Append to the history file unless disabled. Failing to do so doesn't fail the run.
+fn record_history(opts: &Opts, entries: Vec<Entry>) {
+    if opts.no_history || entries.is_empty() {
+        return;
+    }
+    if let Err(e) = history::append(&history_file(opts), &entries) {
+        warn!("{:#}", e);
+    }
+}
+
+/// Solve a part, submit the answer unless it's known to be pointless and record the attempt
+fn submit(opts: &Opts, day: usize, part: usize, force: bool) -> Result<()> {
+    solution::check_part(part)?;
+    let solution = solution::lookup(opts.year(), day)?;
+    let results = runner::run_file(
+        solution,
+        &solution.default_input_path(&opts.inputs()),
+        &[part],
+    )?;
+    let answer = match &results[0].answer {
+        Ok(Answer::Art(_)) => bail!("Art has to be read and submitted by hand"),
+        Ok(answer) => answer.to_string(),
+        Err(e) => bail!("{:#}", e),
+    };
+    info!("Answer of day {} part {}: {}", day, part, answer);
+
+    let submissions_file = opts.inputs().year_dir(opts.year()).join("submissions.json");
+    let mut submissions = Submissions::load(&submissions_file)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    submissions.check(day, part, &answer, now)?;
+    if let Some(reason) = submissions.out_of_bounds(day, part, &answer) {
+        warn!("{}", reason);
+        if !force {
+            bail!("Not submitting an answer that can't be right, use --force to submit anyway");
+        }
+    }
+
+    let response = client(opts)?.submit(day, part, &answer)?;
+    submissions.record(Attempt {
+        day,
+        part,
+        answer,
+        outcome: response.outcome,
+        time: now,
+        wait: response.wait,
+    });
+    submissions.save(&submissions_file)?;
+
+    let wait = response
+        .wait
+        .map(|wait| format!(", wait {}s before submitting again", wait))
+        .unwrap_or_default();
+    match response.outcome {
+        Outcome::Correct => info!("That's the right answer!"),
+        Outcome::Wrong => error!("That's not the right answer{}", wait),
+        Outcome::TooHigh => error!("That's not the right answer, it's too high{}", wait),
+        Outcome::TooLow => error!("That's not the right answer, it's too low{}", wait),
+        Outcome::TooRecent => error!("Submitted too recently{}", wait),
+        Outcome::WrongLevel => error!("Part {} is already solved or still locked", part),
+        Outcome::Unknown => error!("Couldn't understand the response of the website"),
+    }
+    if response.outcome != Outcome::Correct {
+        exit(Failure::SolverFailed.code());
+    }
+    Ok(())
+}
+
+fn solve(
+    day: usize,
+    parts: &[usize],
+    inputs: &InputFiles,
+    input_files: Vec<PathBuf>,
+    answers: Option<&Answers>,
+    opts: &Opts,
+) -> Result<()> {
+    let solution = solution::lookup(opts.year(), day)?;
+
+    debug!(
+        "Solving day {} ({}) parts {:?}",
+        day,
+        solution.title(),
+        parts
+    );
+
+    // Only answers of the real input belong in the history
+    let record = input_files.is_empty();
+    let input_files = if input_files.is_empty() {
+        vec![solution.default_input_path(inputs)]
+    } else {
+        input_files
+    };
+    let environment = Environment::detect();
+    let mut entries = Vec::new();
+
+    // One answer per file and part. Keep going if any fails.
+    let mut records = Vec::with_capacity(input_files.len() * parts.len());
+    // The greatest failure is reported, see [`Failure`]
+    let mut failure = None;
+    for input_file in &input_files {
+        if input_files.len() > 1 {
+            info!("Input file {}", input_file.display());
+        }
+        let results = runner::run_file(solution, input_file, parts);
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                error!("{:#}", e);
+                failure = failure.max(Some(Failure::of(&e)));
+                records.extend(
+                    parts
+                        .iter()
+                        .map(|&part| Record::failed(day, part, input_file, &e)),
+                );
+                continue;
+            }
+        };
+        info!(
+            "Parsed in {:?}{}",
+            results[0].parse_duration,
+            alloc_info(results[0].parse_alloc)
+        );
+
+        for result in &results {
+            records.push(Record::new(result, input_file));
+            if record {
+                entries.extend(Entry::from_result(result, &environment));
+            }
+            let answer = match &result.answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    error!("{:#}", e);
+                    failure = failure.max(Some(Failure::SolverFailed));
+                    continue;
+                }
+            };
+            info!(
+                "Part {} solved in {:?}{}",
+                result.part,
+                result.duration,
+                alloc_info(result.alloc)
+            );
+
+            if let Some(answers) = answers {
+                match answers.check(day, result.part, answer) {
+                    Verdict::Correct => info!("Answer is correct"),
+                    Verdict::Wrong { expected } => {
+                        error!("Wrong answer! Expected {} but got {}", expected, answer);
+                        failure = failure.max(Some(Failure::SolverFailed));
+                    }
+                    Verdict::Unknown => {
+                        warn!("No known answer for day {} part {}", day, result.part)
+                    }
+                }
+            }
+        }
+    }
+
+    record_history(opts, entries);
+    output::print_records(&records, opts.format())?;
+    if let Some(failure) = failure {
+        exit(failure.code());
+    }
+    Ok(())
+}
+
+fn alloc_info(stats: Option<alloc::AllocStats>) -> String {
+    stats
+        .map(|stats| format!(" ({})", stats))
+        .unwrap_or_default()
+}
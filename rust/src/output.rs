@@ -0,0 +1,125 @@
+use anyhow::Result;
+use clap::ArgEnum;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::alloc::AllocStats;
+use crate::answer::Answer;
+use crate::runner::PartResult;
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// A single solved (or failed) part, as written to stdout
+#[derive(Serialize)]
+pub struct Record {
+    pub day: usize,
+    pub part: usize,
+    pub answer: Option<Answer>,
+    /// Nanoseconds spent parsing the input
+    pub parse_duration: u64,
+    /// Nanoseconds spent solving the part
+    pub duration: u64,
+    pub input: String,
+    pub error: Option<String>,
+    /// Allocations while parsing, if profiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_alloc: Option<AllocStats>,
+    /// Allocations while solving, if profiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+impl Record {
+    pub fn new(result: &PartResult, input: &Path) -> Self {
+        Self {
+            day: result.day,
+            part: result.part,
+            answer: result.answer.as_ref().ok().cloned(),
+            parse_duration: result.parse_duration.as_nanos() as u64,
+            duration: result.duration.as_nanos() as u64,
+            input: input.display().to_string(),
+            error: result.answer.as_ref().err().map(|e| format!("{:#}", e)),
+            parse_alloc: result.parse_alloc,
+            alloc: result.alloc,
+        }
+    }
+
+    /// Record for a part that couldn't even be started (e.g. unreadable input)
+    pub fn failed(day: usize, part: usize, input: &Path, error: &anyhow::Error) -> Self {
+        Self {
+            day,
+            part,
+            answer: None,
+            parse_duration: 0,
+            duration: 0,
+            input: input.display().to_string(),
+            error: Some(format!("{:#}", error)),
+            parse_alloc: None,
+            alloc: None,
+        }
+    }
+}
+
+/// Write the records to stdout. Text only prints the plain answers.
+/// Csv gets columns for the allocations of the solvers if any were profiled.
+pub fn print_records(records: &[Record], format: Format) -> Result<()> {
+    match format {
+        Format::Text => {
+            for answer in records.iter().filter_map(|r| r.answer.as_ref()) {
+                println!("{}", answer);
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        Format::Csv => {
+            let profiled = records.iter().any(|r| r.alloc.is_some());
+            println!(
+                "day,part,answer,parse_duration,duration,input,error{}",
+                if profiled {
+                    ",allocations,allocated_bytes,peak_bytes"
+                } else {
+                    ""
+                }
+            );
+            for record in records {
+                let alloc = match (profiled, record.alloc) {
+                    (false, _) => String::new(),
+                    (true, None) => ",,,".to_owned(),
+                    (true, Some(alloc)) => {
+                        format!(",{},{},{}", alloc.allocations, alloc.bytes, alloc.peak)
+                    }
+                };
+                println!(
+                    "{},{},{},{},{},{},{}{}",
+                    record.day,
+                    record.part,
+                    csv_field(
+                        &(record.answer.as_ref())
+                            .map(|answer| answer.to_string())
+                            .unwrap_or_default()
+                    ),
+                    record.parse_duration,
+                    record.duration,
+                    csv_field(&record.input),
+                    csv_field(record.error.as_deref().unwrap_or("")),
+                    alloc
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quote the field if needed
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
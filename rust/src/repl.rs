@@ -0,0 +1,86 @@
+use adventofcode_2021::runner::{read_input, with_input_path};
+use adventofcode_2021::solution::DynSolution;
+use anyhow::Result;
+use std::{
+    any::Any,
+    io::{self, BufRead, Write},
+    path::Path,
+    time::Instant,
+};
+
+const HELP: &str = "\
+part1, part2   Solve a part
+step [n]       Go n steps (default 1) further, if the day has steps
+reset          Go back to the start
+reload         Read and parse the input file again
+help           Show this help
+quit           Leave the repl
+Anything else is passed to the day, e.g. \"show card 3\" on day 4";
+
+/// Parse the input once and answer commands about it until stdin is closed or "quit"
+pub fn repl(solution: &dyn DynSolution, input_file: &Path) -> Result<()> {
+    let mut input = load(solution, input_file)?;
+    let mut step = 0;
+    println!("Day {}: {}", solution.day(), solution.title());
+    println!("Type \"help\" to see the available commands");
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("day{}> ", solution.day());
+        io::stdout().flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        let output = match words.as_slice() {
+            [] => continue,
+            ["quit"] | ["exit"] => break,
+            ["help"] => Ok(HELP.to_owned()),
+            ["part1"] | ["part2"] => {
+                let part = if words[0] == "part1" { 1 } else { 2 };
+                let start = Instant::now();
+                solution
+                    .solve(input.as_ref(), part)
+                    .map(|answer| format!("{} ({:?})", answer, start.elapsed()))
+            }
+            ["step"] | ["step", _] => {
+                let steps = words.get(1).map_or(Ok(1), |n| n.parse::<usize>());
+                match steps {
+                    Ok(steps) => {
+                        step += steps;
+                        solution
+                            .explore(input.as_ref(), step, &["step"])
+                            .unwrap_or_else(|| Ok(format!("Step {}", step)))
+                    }
+                    Err(e) => Err(anyhow!("Invalid amount of steps: {}", e)),
+                }
+            }
+            ["reset"] => {
+                step = 0;
+                Ok("Back at the start".to_owned())
+            }
+            ["reload"] => load(solution, input_file).map(|reloaded| {
+                input = reloaded;
+                format!("Reloaded {}", input_file.display())
+            }),
+            _ => solution
+                .explore(input.as_ref(), step, &words)
+                .unwrap_or_else(|| Err(anyhow!("Unknown command, try \"help\""))),
+        };
+        match output {
+            Ok(output) => println!("{}", output),
+            Err(e) => println!("Error: {:#}", e),
+        }
+    }
+    Ok(())
+}
+
+fn load(solution: &dyn DynSolution, input_file: &Path) -> Result<Box<dyn Any>> {
+    let input = read_input(input_file)?;
+    solution
+        .parse(&input)
+        .map_err(|e| with_input_path(e, input_file))
+}
@@ -0,0 +1,276 @@
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::alloc::{self, AllocStats};
+use crate::answer::Answer;
+use crate::answers::{Answers, Verdict};
+use crate::error::{InputError, ParseError};
+use crate::input;
+use crate::solution::{DynSolution, InputFiles};
+
+/// Outcome of solving a single part of a day
+pub struct PartResult {
+    pub day: usize,
+    pub part: usize,
+    pub title: &'static str,
+    pub answer: Result<Answer>,
+    /// Time spent parsing the input (shared by all parts of a day)
+    pub parse_duration: Duration,
+    /// Time spent solving the part
+    pub duration: Duration,
+    /// Allocations while parsing, if profiled
+    pub parse_alloc: Option<AllocStats>,
+    /// Allocations while solving, if profiled
+    pub alloc: Option<AllocStats>,
+}
+
+/// Parse the input once and solve the given parts, measuring how long each step took
+pub fn run_day(
+    solution: &dyn DynSolution,
+    input: &str,
+    parts: &[usize],
+) -> Result<Vec<PartResult>> {
+    let start = Instant::now();
+    let (parsed, parse_alloc) = alloc::measure(|| solution.parse(input));
+    let parse_duration = start.elapsed();
+    let parsed = parsed.with_context(|| format!("Parsing input of day {}", solution.day()))?;
+
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let (answer, alloc) = alloc::measure(|| solution.solve(parsed.as_ref(), part));
+            let duration = start.elapsed();
+            PartResult {
+                day: solution.day(),
+                part,
+                title: solution.title(),
+                answer: answer
+                    .with_context(|| format!("Solving day {} part {}", solution.day(), part)),
+                parse_duration,
+                duration,
+                parse_alloc,
+                alloc,
+            }
+        })
+        .collect())
+}
+
+/// Read the input file and solve the given parts. Parse errors get the path attached.
+pub fn run_file(
+    solution: &dyn DynSolution,
+    path: &Path,
+    parts: &[usize],
+) -> Result<Vec<PartResult>> {
+    Ok(run_input_file(solution, path, parts)?)
+}
+
+/// [`run_file`] with the typed error, which can be shared by the results of every part
+fn run_input_file(
+    solution: &dyn DynSolution,
+    path: &Path,
+    parts: &[usize],
+) -> Result<Vec<PartResult>, InputError> {
+    let input = read_input(path).map_err(InputError::new)?;
+    run_day(solution, &input, parts).map_err(|e| InputError::new(with_input_path(e, path)))
+}
+
+/// Attach the path of the input file to a [`ParseError`], so it's shown along with the line
+pub fn with_input_path(mut error: anyhow::Error, path: &Path) -> anyhow::Error {
+    if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+        parse_error.path = Some(if path == Path::new("-") {
+            PathBuf::from("<stdin>")
+        } else {
+            path.to_owned()
+        });
+    }
+    error
+}
+
+/// Solve both parts of the days using their default input files.
+/// Failing days don't stop the remaining ones from being solved.
+///
+/// With more than one thread every day and part is solved on its own (parsing the input
+/// for each part), spread across the threads. Results are in day and part order either way.
+pub fn run_all(
+    solutions: &[&dyn DynSolution],
+    inputs: &InputFiles,
+    threads: usize,
+) -> Vec<PartResult> {
+    if threads <= 1 {
+        return solutions
+            .iter()
+            .flat_map(|solution| run_parts(*solution, inputs, &[1, 2]))
+            .collect();
+    }
+
+    let jobs = solutions
+        .iter()
+        .flat_map(|solution| [(*solution, 1), (*solution, 2)])
+        .collect::<Vec<_>>();
+    let next_job = AtomicUsize::new(0);
+    let results = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.min(jobs.len()) {
+            scope.spawn(|| loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(&(solution, part)) = jobs.get(index) else {
+                    break;
+                };
+                let result = run_parts(solution, inputs, &[part]).remove(0);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("Every job is done after the threads are joined"))
+        .collect()
+}
+
+/// Solve the parts of a day using its default input file, turning failure to read
+/// or parse the input into a failed result for each part
+fn run_parts(solution: &dyn DynSolution, inputs: &InputFiles, parts: &[usize]) -> Vec<PartResult> {
+    match run_input_file(solution, &solution.default_input_path(inputs), parts) {
+        Ok(results) => results,
+        Err(e) => parts
+            .iter()
+            .map(|&part| PartResult {
+                day: solution.day(),
+                part,
+                title: solution.title(),
+                answer: Err(e.clone().into()),
+                parse_duration: Duration::ZERO,
+                duration: Duration::ZERO,
+                parse_alloc: None,
+                alloc: None,
+            })
+            .collect(),
+    }
+}
+
+/// Read an input file or stdin if the path is "-"
+pub fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .context("Reading input from stdin")?;
+        return Ok(input::normalize(&input).into_owned());
+    }
+    let input = fs::read_to_string(path)
+        .with_context(|| format!("Reading input file {}", path.display()))?;
+    Ok(input::normalize(&input).into_owned())
+}
+
+/// Print the drawings of art answers, which tables only show a placeholder of
+pub fn print_art(results: &[PartResult]) {
+    for result in results {
+        if let Ok(art @ Answer::Art(_)) = &result.answer {
+            println!("\nDay {} part {}:\n{}", result.day, result.part, art);
+        }
+    }
+}
+
+/// Time spent on all results of [`run_all`]. On a single thread the parse time of each
+/// day is counted only once, on more threads every part parsed its input on its own.
+pub fn total_duration(results: &[PartResult], threads: usize) -> Duration {
+    let mut total: Duration = results.iter().map(|r| r.duration).sum();
+    let mut last_day = None;
+    for result in results {
+        if threads > 1 || last_day != Some(result.day) {
+            total += result.parse_duration;
+            last_day = Some(result.day);
+        }
+    }
+    total
+}
+
+/// Print a table of all answers and timings, followed by the failed parts.
+/// If answers are given, every answer is checked against them as well.
+/// Allocations of the solvers are shown if they were profiled.
+/// `threads` is the number [`run_all`] solved on.
+pub fn print_summary(results: &[PartResult], answers: Option<&Answers>, threads: usize) {
+    let profiled = results.iter().any(|r| r.alloc.is_some());
+    let title_width = results.iter().map(|r| r.title.len()).max().unwrap_or(0);
+    let answer_width = results
+        .iter()
+        .map(|r| match &r.answer {
+            Ok(answer) => answer.inline().len(),
+            Err(_) => "ERROR".len(),
+        })
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "Day  {:title_width$}  Part  {:>answer_width$}  {:>12}  {:>12}{}{}",
+        "Title",
+        "Answer",
+        "Parse",
+        "Solve",
+        if profiled {
+            format!("  {:>8}  {:>12}  {:>12}", "Allocs", "Bytes", "Peak")
+        } else {
+            String::new()
+        },
+        if answers.is_some() { "  Check" } else { "" },
+        title_width = title_width,
+        answer_width = answer_width
+    );
+    for result in results {
+        let check = match (answers, &result.answer) {
+            (None, _) => String::new(),
+            (Some(_), Err(_)) => "  -".to_owned(),
+            (Some(answers), Ok(answer)) => match answers.check(result.day, result.part, answer) {
+                Verdict::Correct => "  ok".to_owned(),
+                Verdict::Wrong { expected } => format!("  WRONG (expected {})", expected),
+                Verdict::Unknown => "  unknown".to_owned(),
+            },
+        };
+        let alloc = match (profiled, result.alloc) {
+            (false, _) => String::new(),
+            (true, None) => format!("  {:>8}  {:>12}  {:>12}", "-", "-", "-"),
+            (true, Some(alloc)) => format!(
+                "  {:>8}  {:>12}  {:>12}",
+                alloc.allocations, alloc.bytes, alloc.peak
+            ),
+        };
+        println!(
+            "{:>3}  {:title_width$}  {:>4}  {:>answer_width$}  {:>12}  {:>12}{}{}",
+            result.day,
+            result.title,
+            result.part,
+            match &result.answer {
+                Ok(answer) => answer.inline(),
+                Err(_) => "ERROR".to_owned(),
+            },
+            format!("{:?}", result.parse_duration),
+            format!("{:?}", result.duration),
+            alloc,
+            check,
+            title_width = title_width,
+            answer_width = answer_width
+        );
+    }
+    println!("Total: {:?}", total_duration(results, threads));
+    print_art(results);
+
+    for result in results {
+        if let Err(e) = &result.answer {
+            error!("Day {} part {} failed: {:#}", result.day, result.part, e);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use adventofcode_2021::solution::{InputFiles, DEFAULT_YEAR};
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+
+/// Where the day modules live, so days can be generated from any directory
+pub const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// Generate a new day module, register it and create empty input files for it.
+/// The modules are named after the day only, so this is for days of [`DEFAULT_YEAR`].
+pub fn new_day(day: usize, title: &str, src_dir: &Path, inputs: &InputFiles) -> Result<()> {
+    let module_file = src_dir.join(format!("day{}.rs", day));
+    ensure!(
+        !module_file.exists(),
+        "{} already exists!",
+        module_file.display()
+    );
+
+    fs::write(&module_file, module_template(day, title))
+        .with_context(|| format!("Writing {}", module_file.display()))?;
+    info!("Created {}", module_file.display());
+
+    register_module(&src_dir.join("lib.rs"), day)?;
+    register_solution(&src_dir.join("solution.rs"), day)?;
+
+    let input_dir = inputs.year_dir(DEFAULT_YEAR);
+    fs::create_dir_all(&input_dir)
+        .with_context(|| format!("Creating input directory {}", input_dir.display()))?;
+    for input_file in [
+        inputs.path(DEFAULT_YEAR, day),
+        inputs.example_path(DEFAULT_YEAR, day),
+    ] {
+        create_empty(&input_file)?;
+    }
+    Ok(())
+}
+
+/// Add "pub mod dayN;" next to the other day modules
+fn register_module(lib_file: &Path, day: usize) -> Result<()> {
+    edit_file(lib_file, |content| {
+        insert_sorted(content, &format!("pub mod day{};", day), day, |line| {
+            parse_day(line.strip_prefix("pub mod day")?.strip_suffix(';')?)
+        })
+    })
+}
+
+/// Add the day's solution to the registry
+fn register_solution(solution_file: &Path, day: usize) -> Result<()> {
+    edit_file(solution_file, |content| {
+        insert_sorted(
+            content,
+            &format!("    &crate::day{}::Day{},", day, day),
+            day,
+            |line| parse_day(line.strip_prefix("    &crate::day")?.split("::").next()?),
+        )
+    })
+}
+
+fn parse_day(text: &str) -> Option<usize> {
+    text.parse().ok()
+}
+
+/// Insert the line among a block of lines that belong to days, keeping them sorted
+fn insert_sorted<F>(content: &str, new_line: &str, day: usize, day_of_line: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut lines = content.lines().collect::<Vec<_>>();
+    let day_lines = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, day_of_line(line)?)))
+        .collect::<Vec<_>>();
+    ensure!(
+        !day_lines.is_empty(),
+        "No place found to insert {:?}",
+        new_line
+    );
+    ensure!(
+        day_lines.iter().all(|(_, d)| *d != day),
+        "Day {} is already registered!",
+        day
+    );
+
+    let index = match day_lines.iter().find(|(_, d)| *d > day) {
+        Some((i, _)) => *i,
+        None => day_lines.last().unwrap().0 + 1,
+    };
+    lines.insert(index, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn edit_file<F>(path: &Path, edit: F) -> Result<()>
+where
+    F: FnOnce(&str) -> Result<String>,
+{
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+    let content = edit(&content).with_context(|| format!("Editing {}", path.display()))?;
+    fs::write(path, content).with_context(|| format!("Writing {}", path.display()))?;
+    info!("Updated {}", path.display());
+    Ok(())
+}
+
+fn create_empty(path: &Path) -> Result<()> {
+    if path.exists() {
+        warn!("Keeping existing {}", path.display());
+        return Ok(());
+    }
+    fs::write(path, "").with_context(|| format!("Creating {}", path.display()))?;
+    info!("Created {}", path.display());
+    Ok(())
+}
+
+fn module_template(day: usize, title: &str) -> String {
+    format!(
+        r#"use crate::answer::Answer;
+use crate::input;
+use crate::solution::Solution;
+
+pub fn parse_input(input: &str) -> anyhow::Result<Vec<String>> {{
+    Ok(input::lines(input).map(|l| l.text.to_owned()).collect())
+}}
+
+pub fn solve_part_1(_lines: &[String]) -> anyhow::Result<Answer> {{
+    Err(anyhow!("Part 1 is not solved yet!"))
+}}
+
+pub fn solve_part_2(_lines: &[String]) -> anyhow::Result<Answer> {{
+    Err(anyhow!("Part 2 is not solved yet!"))
+}}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+
+    const YEAR: usize = {year};
+    const DAY: usize = {day};
+    const TITLE: &'static str = {title:?};
+
+    fn parse(input: &str) -> anyhow::Result<Vec<String>> {{
+        parse_input(input)
+    }}
+
+    fn part1(lines: &Vec<String>) -> anyhow::Result<Answer> {{
+        solve_part_1(lines)
+    }}
+
+    fn part2(lines: &Vec<String>) -> anyhow::Result<Answer> {{
+        solve_part_2(lines)
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // TODO: Paste the example from the puzzle description
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn part_1_example() {{
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_1(&input).unwrap().to_string(), "TODO");
+    }}
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn part_2_example() {{
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part_2(&input).unwrap().to_string(), "TODO");
+    }}
+}}
+"#,
+        year = DEFAULT_YEAR,
+        day = day,
+        title = title,
+    )
+}
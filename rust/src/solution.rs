@@ -0,0 +1,301 @@
+use anyhow::Result;
+use std::{any::Any, ops::RangeInclusive, path::PathBuf};
+
+use crate::answer::Answer;
+use crate::error::{InvalidDay, InvalidPart, NotImplemented};
+
+/// The year used unless another one is chosen
+pub const DEFAULT_YEAR: usize = 2021;
+
+/// The first Advent of Code
+pub const FIRST_YEAR: usize = 2015;
+
+/// All implemented days, ordered by year and day
+pub static REGISTRY: &[&dyn DynSolution] = &[
+    &crate::day1::Day1,
+    &crate::day2::Day2,
+    &crate::day3::Day3,
+    &crate::day4::Day4,
+    &crate::day5::Day5,
+    &crate::day6::Day6,
+    &crate::day7::Day7,
+];
+
+/// Look up the solution for a given day in the [`REGISTRY`]
+pub fn find(year: usize, day: usize) -> Option<&'static dyn DynSolution> {
+    REGISTRY
+        .iter()
+        .copied()
+        .find(|solution| solution.year() == year && solution.day() == day)
+}
+
+/// Years with at least one implemented day
+pub fn years() -> Vec<usize> {
+    let mut years = REGISTRY.iter().map(|s| s.year()).collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+/// Days with puzzles. Since 2025 there are only 12.
+pub fn puzzle_days(year: usize) -> RangeInclusive<usize> {
+    if year >= 2025 {
+        1..=12
+    } else {
+        1..=25
+    }
+}
+
+/// Whether there is a puzzle for the day, regardless of whether it's implemented
+pub fn check_day(year: usize, day: usize) -> Result<(), InvalidDay> {
+    if year >= FIRST_YEAR && puzzle_days(year).contains(&day) {
+        Ok(())
+    } else {
+        Err(InvalidDay { year, day })
+    }
+}
+
+/// Whether the part exists, only 1 and 2 do
+pub fn check_part(part: usize) -> Result<(), InvalidPart> {
+    if part == 1 || part == 2 {
+        Ok(())
+    } else {
+        Err(InvalidPart(part))
+    }
+}
+
+/// All implemented days of a year, an error telling which years exist if there are none
+pub fn solutions(year: usize) -> Result<Vec<&'static dyn DynSolution>> {
+    let solutions = (REGISTRY.iter().copied())
+        .filter(|solution| solution.year() == year)
+        .collect::<Vec<_>>();
+    if solutions.is_empty() {
+        return Err(NotImplemented {
+            year,
+            day: None,
+            implemented: years(),
+        }
+        .into());
+    }
+    Ok(solutions)
+}
+
+/// The solution of a day, an error telling which days exist if it's not implemented
+pub fn lookup(year: usize, day: usize) -> Result<&'static dyn DynSolution> {
+    check_day(year, day)?;
+    let days = solutions(year)?
+        .iter()
+        .map(|solution| solution.day())
+        .collect::<Vec<_>>();
+    find(year, day).ok_or_else(|| {
+        NotImplemented {
+            year,
+            day: Some(day),
+            implemented: days,
+        }
+        .into()
+    })
+}
+
+/// Sorted numbers as ranges, e.g. "1-5, 7"
+pub(crate) fn format_numbers(numbers: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &number in numbers {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == number => *end = number,
+            _ => ranges.push((number, number)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| match end - start {
+            0 => start.to_string(),
+            1 => format!("{}, {}", start, end),
+            _ => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// File names of the inputs, "{day}" gets replaced by the day ("{day:02}" to zero pad it)
+pub const DEFAULT_INPUT_PATTERN: &str = "day{day}.txt";
+
+/// Where the input files are: A directory per year in `dir`, named after the year, with
+/// files named after the pattern in it
+#[derive(Clone, Debug)]
+pub struct InputFiles {
+    pub dir: PathBuf,
+    pub pattern: String,
+}
+
+impl InputFiles {
+    /// Input files named like "2021/day5.txt"
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            pattern: DEFAULT_INPUT_PATTERN.to_owned(),
+        }
+    }
+
+    /// Directory of the inputs of a year. Also has the files about them, like the answers.
+    pub fn year_dir(&self, year: usize) -> PathBuf {
+        self.dir.join(year.to_string())
+    }
+
+    /// Input file of a day, also for days that aren't implemented yet
+    pub fn path(&self, year: usize, day: usize) -> PathBuf {
+        self.year_dir(year).join(
+            self.pattern
+                .replace("{day}", &day.to_string())
+                .replace("{day:02}", &format!("{:02}", day)),
+        )
+    }
+
+    /// Example input of a day, named like the input with ".example" before the extension
+    pub fn example_path(&self, year: usize, day: usize) -> PathBuf {
+        let path = self.path(year, day);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match path.extension() {
+            Some(extension) => format!("{}.example.{}", stem, extension.to_string_lossy()),
+            None => format!("{}.example", stem),
+        };
+        path.with_file_name(name)
+    }
+}
+
+/// The solution of a single day
+pub trait Solution {
+    /// Puzzle input after parsing. Shared by both parts.
+    type Input: 'static;
+
+    const YEAR: usize;
+    const DAY: usize;
+    const TITLE: &'static str;
+
+    /// Input file used if none is specified
+    fn default_input_path(inputs: &InputFiles) -> PathBuf {
+        inputs.path(Self::YEAR, Self::DAY)
+    }
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    /// Day specific repl command like `show card 3`, given as words. The repl counts the
+    /// `step` commands, so a day can show its state after that many steps (e.g. called
+    /// numbers). A plain `step` command asks what happened in the latest step.
+    /// Returns `None` for unknown commands.
+    fn explore(_input: &Self::Input, _step: usize, _command: &[&str]) -> Option<Result<String>> {
+        None
+    }
+}
+
+/// Object safe counterpart of [`Solution`], so all days can be kept in the [`REGISTRY`]
+pub trait DynSolution: Sync {
+    fn year(&self) -> usize;
+    fn day(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn default_input_path(&self, inputs: &InputFiles) -> PathBuf;
+
+    /// Parse the input into the type erased [`Solution::Input`]
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    /// Solve a part using input returned by [`DynSolution::parse`]
+    fn solve(&self, input: &dyn Any, part: usize) -> Result<Answer>;
+    /// See [`Solution::explore`]
+    fn explore(&self, input: &dyn Any, step: usize, command: &[&str]) -> Option<Result<String>>;
+
+    /// Parse and solve a part in one go
+    fn solve_str(&self, input: &str, part: usize) -> Result<Answer> {
+        self.solve(self.parse(input)?.as_ref(), part)
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn year(&self) -> usize {
+        S::YEAR
+    }
+
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn default_input_path(&self, inputs: &InputFiles) -> PathBuf {
+        S::default_input_path(inputs)
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(<S as Solution>::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: usize) -> Result<Answer> {
+        let input = downcast_input::<S>(input)?;
+        match part {
+            1 => S::part1(input),
+            2 => S::part2(input),
+            _ => Err(InvalidPart(part).into()),
+        }
+    }
+
+    fn explore(&self, input: &dyn Any, step: usize, command: &[&str]) -> Option<Result<String>> {
+        match downcast_input::<S>(input) {
+            Ok(input) => S::explore(input, step, command),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+fn downcast_input<S: Solution>(input: &dyn Any) -> Result<&S::Input> {
+    input
+        .downcast_ref::<S::Input>()
+        .ok_or(anyhow!("Input was not parsed for day {}!", S::DAY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_file_names() {
+        let inputs = InputFiles::new("input");
+        assert_eq!(inputs.path(2021, 5), PathBuf::from("input/2021/day5.txt"));
+        assert_eq!(
+            inputs.example_path(2021, 5),
+            PathBuf::from("input/2021/day5.example.txt")
+        );
+
+        let inputs = InputFiles {
+            dir: PathBuf::from("inputs"),
+            pattern: "{day:02}/input".to_owned(),
+        };
+        assert_eq!(inputs.path(2020, 12), PathBuf::from("inputs/2020/12/input"));
+        assert_eq!(inputs.path(2020, 3), PathBuf::from("inputs/2020/03/input"));
+        assert_eq!(
+            inputs.example_path(2020, 12),
+            PathBuf::from("inputs/2020/12/input.example")
+        );
+    }
+
+    #[test]
+    fn validate_days() {
+        let error = |year, day| lookup(year, day).err().unwrap().to_string();
+        assert_eq!(lookup(2021, 1).unwrap().title(), "Sonar Sweep");
+        assert_eq!(
+            error(2021, 26),
+            "Invalid day \"26\" Only 1-25 exist in 2021!"
+        );
+        assert!(check_day(2025, 13).is_err());
+        assert!(check_day(2014, 1).is_err());
+        assert!(error(2021, 25)
+            .starts_with("Day 25 of 2021 is not implemented yet! Implemented days: 1-"));
+        assert!(error(2020, 1).starts_with("Nothing implemented for 2020"));
+    }
+
+    #[test]
+    fn number_ranges() {
+        assert_eq!(format_numbers(&[]), "");
+        assert_eq!(format_numbers(&[1, 2, 3, 5, 7, 8, 10]), "1-3, 5, 7, 8, 10");
+    }
+}
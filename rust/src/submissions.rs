@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// What the website said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted before the wait time of the last attempt was over
+    TooRecent,
+    /// The part was solved before or isn't unlocked yet
+    WrongLevel,
+    /// Response that couldn't be understood
+    Unknown,
+}
+
+impl Outcome {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+/// Response of the website to a submission
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub outcome: Outcome,
+    /// Seconds to wait before the next submission
+    pub wait: Option<u64>,
+}
+
+impl Response {
+    /// Understand the html page returned after submitting
+    pub fn parse(html: &str) -> Self {
+        let outcome = if html.contains("That's the right answer") {
+            Outcome::Correct
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if html.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if html.contains("You gave an answer too recently") {
+            Outcome::TooRecent
+        } else if html.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        };
+        Self {
+            outcome,
+            wait: parse_wait(html),
+        }
+    }
+}
+
+/// Find "You have 1m 5s left to wait" or "please wait 5 minutes before trying again"
+fn parse_wait(html: &str) -> Option<u64> {
+    if let Some(start) = html.find("You have ") {
+        let rest = &html[start + "You have ".len()..];
+        let time = &rest[..rest.find(" left to wait")?];
+        return time.split(' ').try_fold(0, |seconds, word| {
+            let (number, unit) = word.split_at(word.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(seconds + number * 60 * 60),
+                "m" => Some(seconds + number * 60),
+                "s" => Some(seconds + number),
+                _ => None,
+            }
+        });
+    }
+    let start = html
+        .find("please wait ")
+        .or_else(|| html.find("Please wait "))?;
+    let rest = &html[start + "please wait ".len()..];
+    let (amount, rest) = rest.split_once(' ')?;
+    let amount = match amount {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+    rest.starts_with("minute").then_some(amount * 60)
+}
+
+/// A submitted answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Unix timestamp of the submission
+    pub time: u64,
+    /// Seconds to wait before the next submission
+    pub wait: Option<u64>,
+}
+
+/// Every answer submitted so far, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Submissions(Vec<Attempt>);
+
+impl Submissions {
+    /// Load the submissions file, starting empty if there is none yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Reading submissions file {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Parsing submissions file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Writing submissions file {}", path.display()))
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.0.push(attempt);
+    }
+
+    fn attempts(&self, day: usize, part: usize) -> impl Iterator<Item = &Attempt> {
+        self.0
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    /// Unix timestamp until which the website won't accept answers
+    pub fn wait_until(&self) -> Option<u64> {
+        let last = self.0.last()?;
+        Some(last.time + last.wait?)
+    }
+
+    /// Refuse submissions that are known to be pointless: Answers that were wrong
+    /// before, parts that are already solved and submitting while having to wait
+    pub fn check(&self, day: usize, part: usize, answer: &str, now: u64) -> Result<()> {
+        if let Some(wait_until) = self.wait_until().filter(|&until| until > now) {
+            bail!(
+                "Submitted too recently, wait {}s before trying again",
+                wait_until - now
+            );
+        }
+        for attempt in self.attempts(day, part) {
+            if attempt.outcome == Outcome::Correct {
+                bail!(
+                    "Day {} part {} is already solved with {}",
+                    day,
+                    part,
+                    attempt.answer
+                );
+            }
+            if attempt.outcome.is_wrong() && attempt.answer == answer {
+                bail!("{} was already submitted and is wrong", answer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lowest and highest answer that could still be right, known from
+    /// "too low" and "too high" responses. Exclusive.
+    pub fn bounds(&self, day: usize, part: usize) -> (Option<i128>, Option<i128>) {
+        let bound = |outcome| {
+            self.attempts(day, part)
+                .filter(move |attempt| attempt.outcome == outcome)
+                .filter_map(|attempt| attempt.answer.parse::<i128>().ok())
+        };
+        (bound(Outcome::TooLow).max(), bound(Outcome::TooHigh).min())
+    }
+
+    /// Explain why the answer can't be right if it's outside the known bounds
+    pub fn out_of_bounds(&self, day: usize, part: usize, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok()?;
+        match self.bounds(day, part) {
+            (Some(low), _) if number <= low => {
+                Some(format!("{} is too low, {} already was", answer, low))
+            }
+            (_, Some(high)) if number >= high => {
+                Some(format!("{} is too high, {} already was", answer, high))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(answer: &str, outcome: Outcome, wait: Option<u64>) -> Attempt {
+        Attempt {
+            day: 1,
+            part: 2,
+            answer: answer.to_owned(),
+            outcome,
+            time: 1000,
+            wait,
+        }
+    }
+
+    #[test]
+    fn parse_responses() {
+        assert_eq!(
+            Response::parse(
+                "<p>That's not the right answer; your answer is too high. \
+                 Please wait one minute before trying again.</p>"
+            ),
+            Response {
+                outcome: Outcome::TooHigh,
+                wait: Some(60)
+            }
+        );
+        assert_eq!(
+            Response::parse(
+                "<p>You gave an answer too recently; you have to wait after \
+                 submitting an answer before trying again.  You have 1m 5s left to wait.</p>"
+            ),
+            Response {
+                outcome: Outcome::TooRecent,
+                wait: Some(65)
+            }
+        );
+        assert_eq!(
+            Response::parse("<p>That's the right answer! You are one gold star closer.</p>")
+                .outcome,
+            Outcome::Correct
+        );
+    }
+
+    #[test]
+    fn refuse_pointless_submissions() {
+        let mut submissions = Submissions::default();
+        submissions.record(attempt("100", Outcome::TooLow, Some(60)));
+        submissions.record(attempt("500", Outcome::TooHigh, Some(60)));
+
+        assert!(submissions.check(1, 2, "200", 1030).is_err());
+        assert!(submissions.check(1, 2, "500", 1060).is_err());
+        assert!(submissions.check(1, 2, "200", 1060).is_ok());
+        assert!(submissions.check(1, 1, "500", 1060).is_ok());
+
+        assert_eq!(submissions.bounds(1, 2), (Some(100), Some(500)));
+        assert!(submissions.out_of_bounds(1, 2, "99").is_some());
+        assert!(submissions.out_of_bounds(1, 2, "501").is_some());
+        assert_eq!(submissions.out_of_bounds(1, 2, "300"), None);
+
+        submissions.record(attempt("300", Outcome::Correct, None));
+        assert!(submissions.check(1, 2, "301", 2000).is_err());
+    }
+}
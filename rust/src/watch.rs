@@ -0,0 +1,139 @@
+use adventofcode_2021::runner;
+use adventofcode_2021::solution::DynSolution;
+use anyhow::Result;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Clears the terminal and moves the cursor to the top left
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
+
+/// Solve the input files again whenever one of them changes. Runs until interrupted.
+///
+/// The source file is only watched to point out that the binary is outdated,
+/// since picking up code changes requires a rebuild.
+pub fn watch(
+    solution: &dyn DynSolution,
+    input_files: &[PathBuf],
+    source_file: &Path,
+    parts: &[usize],
+    interval: Duration,
+) -> Result<()> {
+    let mut modified = HashMap::new();
+    let mut previous_answers = HashMap::new();
+    let started = SystemTime::now();
+
+    loop {
+        let current = input_files
+            .iter()
+            .map(|path| (path.clone(), modified_time(path)))
+            .collect::<HashMap<_, _>>();
+        if current != modified {
+            print!("{}", CLEAR_SCREEN);
+            println!("Day {}: {}", solution.day(), solution.title());
+            for input_file in input_files {
+                run(solution, input_file, parts, &mut previous_answers);
+            }
+            if modified_time(source_file).is_some_and(|time| time > started) {
+                println!();
+                println!(
+                    "{} changed since watching started, rebuild to use the new code",
+                    source_file.display()
+                );
+            }
+            println!();
+            println!("Watching for changes, press Ctrl-C to stop");
+            modified = current;
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Solve one input file and print each answer along with how it changed since the last run
+fn run(
+    solution: &dyn DynSolution,
+    input_file: &Path,
+    parts: &[usize],
+    previous_answers: &mut HashMap<(PathBuf, usize), String>,
+) {
+    println!();
+    println!("{}", input_file.display());
+    let results = match runner::run_file(solution, input_file, parts) {
+        Ok(results) => results,
+        Err(e) => {
+            println!("{:#}", e);
+            return;
+        }
+    };
+    println!("  Parsed in {:?}", results[0].parse_duration);
+
+    for result in results {
+        match result.answer {
+            Ok(answer) => {
+                let answer = answer.to_string();
+                println!("  Part {}: {} ({:?})", result.part, answer, result.duration);
+                let key = (input_file.to_owned(), result.part);
+                if let Some(change) = describe_change(previous_answers.get(&key), &answer) {
+                    println!("{}", change);
+                }
+                previous_answers.insert(key, answer);
+            }
+            Err(e) => println!("  Part {}: {:#}", result.part, e),
+        }
+    }
+}
+
+/// Tell how an answer differs from the previous one, line by line for multi-line answers.
+/// Nothing to tell on the first run.
+fn describe_change(previous: Option<&String>, answer: &str) -> Option<String> {
+    let previous = previous?;
+    if previous == answer {
+        return Some("    unchanged".to_owned());
+    }
+    if !previous.contains('\n') && !answer.contains('\n') {
+        return Some(format!("    changed, was {}", previous));
+    }
+    let mut diff = String::from("    changed:");
+    let (old_lines, new_lines) = (previous.lines().collect::<Vec<_>>(), answer.lines());
+    for (i, new_line) in new_lines.enumerate() {
+        match old_lines.get(i) {
+            Some(old_line) if *old_line == new_line => diff += &format!("\n      {}", new_line),
+            Some(old_line) => diff += &format!("\n    - {}\n    + {}", old_line, new_line),
+            None => diff += &format!("\n    + {}", new_line),
+        }
+    }
+    for old_line in old_lines.iter().skip(answer.lines().count()) {
+        diff += &format!("\n    - {}", old_line);
+    }
+    Some(diff)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_changes() {
+        assert_eq!(describe_change(None, "5"), None);
+        assert_eq!(
+            describe_change(Some(&"5".to_owned()), "5").unwrap(),
+            "    unchanged"
+        );
+        assert_eq!(
+            describe_change(Some(&"5".to_owned()), "7").unwrap(),
+            "    changed, was 5"
+        );
+        assert_eq!(
+            describe_change(Some(&"#.\n.#".to_owned()), "#.\n##\n#.").unwrap(),
+            "    changed:\n      #.\n    - .#\n    + ##\n    + #."
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Needs its own test binary, as the counting allocator has to be the global one
+
+use adventofcode_2021::alloc::{self, CountingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn count_allocations() {
+    let (_, stats) = alloc::measure(|| Vec::<u8>::with_capacity(100));
+    assert!(stats.is_none(), "Not counting before being enabled");
+
+    alloc::enable();
+    let (sum, stats) = alloc::measure(|| {
+        let first = vec![1u8; 1000];
+        drop(first);
+        let second = vec![2u8; 600];
+        second.iter().map(|&n| n as usize).sum::<usize>()
+    });
+    let stats = stats.unwrap();
+    assert_eq!(sum, 1200);
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.bytes, 1600);
+    assert_eq!(stats.peak, 1000);
+}
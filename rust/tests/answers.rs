@@ -0,0 +1,57 @@
+//! Solves the real puzzle inputs and compares them with the answers recorded in
+//! input/2021/answers.toml
+
+use adventofcode_2021::answer::Answer;
+use adventofcode_2021::answers::{Answers, Verdict};
+use adventofcode_2021::runner::{read_input, run_all, run_day, PartResult};
+use adventofcode_2021::solution::{self, InputFiles, DEFAULT_YEAR, REGISTRY};
+
+const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../input");
+
+#[test]
+fn real_inputs_match_recorded_answers() {
+    let inputs = InputFiles::new(INPUT_DIR);
+    let answers = Answers::load(&inputs.year_dir(DEFAULT_YEAR).join("answers.toml")).unwrap();
+
+    let mut failures = Vec::new();
+    for solution in solution::solutions(DEFAULT_YEAR).unwrap() {
+        let day = solution.day();
+        // Days without recorded answers aren't solved yet
+        if answers.expected(day, 1).is_none() && answers.expected(day, 2).is_none() {
+            continue;
+        }
+
+        let input = read_input(&solution.default_input_path(&inputs)).unwrap();
+        for result in run_day(solution, &input, &[1, 2]).unwrap() {
+            let answer = match &result.answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    failures.push(format!("Day {} part {}: {:#}", day, result.part, e));
+                    continue;
+                }
+            };
+            if let Verdict::Wrong { expected } = answers.check(day, result.part, answer) {
+                failures.push(format!(
+                    "Day {} part {}: expected {} but got {}",
+                    day, result.part, expected, answer
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn parallel_results_are_in_order() {
+    fn answers(results: Vec<PartResult>) -> Vec<(usize, usize, Result<Answer, String>)> {
+        results
+            .into_iter()
+            .map(|r| (r.day, r.part, r.answer.map_err(|e| e.to_string())))
+            .collect()
+    }
+    let inputs = InputFiles::new(INPUT_DIR);
+    let sequential = answers(run_all(REGISTRY, &inputs, 1));
+    let parallel = answers(run_all(REGISTRY, &inputs, 4));
+    assert_eq!(sequential.len(), REGISTRY.len() * 2);
+    assert_eq!(parallel, sequential);
+}
@@ -0,0 +1,98 @@
+use adventofcode_2021::client::{cached_input, fetch_input, Client};
+use adventofcode_2021::solution::{InputFiles, DEFAULT_YEAR};
+use adventofcode_2021::submissions::{Outcome, Response};
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+/// Serve a single request with the given body. Returns the base url and a handle that
+/// yields the request line, the headers and the request body (if any).
+fn serve_once(body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut request = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            request.push(line.to_owned());
+        }
+        let content_length = request
+            .iter()
+            .find_map(|line| line.strip_prefix("Content-Length: "))
+            .map_or(0, |len| len.parse().unwrap());
+        if content_length > 0 {
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push(String::from_utf8(content).unwrap());
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        request
+    });
+    (base_url, handle)
+}
+
+#[test]
+fn fetch_downloads_once() {
+    let input_dir = std::env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&input_dir);
+    fs::create_dir_all(input_dir.join("2021")).unwrap();
+    // Like created by the new command
+    fs::write(input_dir.join("2021/day6.txt"), "").unwrap();
+
+    let inputs = InputFiles::new(&input_dir);
+    assert_eq!(cached_input(&inputs, DEFAULT_YEAR, 6), None);
+
+    let (base_url, server) = serve_once("3,4,3,1,2\n");
+    let client = Client::new(&base_url, "secret\n", DEFAULT_YEAR);
+    let path = fetch_input(&client, &inputs, 6).unwrap();
+    assert_eq!(path, input_dir.join("2021/day6.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,3,1,2\n");
+
+    let request = server.join().unwrap();
+    assert_eq!(request[0], "GET /2021/day/6/input HTTP/1.1");
+    assert!(request.iter().any(|line| line == "Cookie: session=secret"));
+
+    // The server is gone, so this only works from the cache
+    assert_eq!(fetch_input(&client, &inputs, 6).unwrap(), path);
+    assert_eq!(cached_input(&inputs, DEFAULT_YEAR, 6), Some(path));
+
+    fs::remove_dir_all(&input_dir).unwrap();
+}
+
+#[test]
+fn submit_posts_answer() {
+    let (base_url, server) = serve_once(
+        "<article><p>That's not the right answer; your answer is too low. \
+         Please wait one minute before trying again.</p></article>",
+    );
+    let response = Client::new(&base_url, "secret", DEFAULT_YEAR)
+        .submit(3, 2, "1234")
+        .unwrap();
+    assert_eq!(
+        response,
+        Response {
+            outcome: Outcome::TooLow,
+            wait: Some(60)
+        }
+    );
+
+    let request = server.join().unwrap();
+    assert_eq!(request[0], "POST /2021/day/3/answer HTTP/1.1");
+    assert_eq!(request.last().unwrap(), "level=2&answer=1234");
+}